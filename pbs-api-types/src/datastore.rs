@@ -19,8 +19,8 @@ use proxmox_schema::{
 };
 
 use crate::{
-    Authid, BACKUP_ID_RE, BACKUP_NS_RE, BACKUP_TIME_RE, BACKUP_TYPE_RE, CryptMode,
-    DATASTORE_NOTIFY_STRING_SCHEMA, Fingerprint, GC_SCHEDULE_SCHEMA,
+    Authid, BACKUP_ID_RE, BACKUP_NS_RE, BACKUP_TIME_RE, BACKUP_TYPE_RE, CALENDAR_EVENT_FORMAT,
+    CryptMode, DATASTORE_NOTIFY_STRING_SCHEMA, Fingerprint, GC_SCHEDULE_SCHEMA,
     GROUP_OR_SNAPSHOT_PATH_REGEX_STR, GroupFilter, MaintenanceMode, MaintenanceType,
     PROXMOX_SAFE_ID_FORMAT, PROXMOX_SAFE_ID_REGEX_STR, PRUNE_SCHEDULE_SCHEMA, SHA256_HEX_REGEX,
     SINGLE_LINE_COMMENT_SCHEMA, SNAPSHOT_PATH_REGEX_STR, UPID, Userid,
@@ -432,9 +432,7 @@ impl FromStr for DatastoreBackendConfig {
 
 pub const COUNTER_RESET_SCHEDULE_SCHEMA: Schema =
     StringSchema::new("Reset notification threshold related counters at specified schedule.")
-        .format(&ApiStringFormat::VerifyFn(
-            proxmox_time::verify_calendar_event,
-        ))
+        .format(&CALENDAR_EVENT_FORMAT)
         .type_text("<calendar-event>")
         .schema();
 
@@ -665,6 +663,10 @@ impl DataStoreConfig {
             None => { /* always OK  */ }
         }
 
+        if let Some(new_mode) = &new_mode {
+            new_mode.check_window()?;
+        }
+
         let new_mode = match new_mode {
             Some(new_mode) => Some(
                 proxmox_schema::property_string::PropertyString::new(new_mode)
@@ -12,8 +12,8 @@ use proxmox_schema::*;
 
 use crate::{
     Authid, BACKUP_GROUP_SCHEMA, BACKUP_NAMESPACE_SCHEMA, BACKUP_NS_RE, BackupNamespace,
-    BackupType, CRYPT_KEY_ID_SCHEMA, DATASTORE_SCHEMA, DRIVE_NAME_SCHEMA, MEDIA_POOL_NAME_SCHEMA,
-    NS_MAX_DEPTH_REDUCED_SCHEMA, NotificationMode, PROXMOX_SAFE_ID_FORMAT,
+    BackupType, CALENDAR_EVENT_FORMAT, CRYPT_KEY_ID_SCHEMA, DATASTORE_SCHEMA, DRIVE_NAME_SCHEMA,
+    MEDIA_POOL_NAME_SCHEMA, NS_MAX_DEPTH_REDUCED_SCHEMA, NotificationMode, PROXMOX_SAFE_ID_FORMAT,
     PROXMOX_SAFE_ID_REGEX_STR, REMOTE_ID_SCHEMA, RateLimitConfig, SINGLE_LINE_COMMENT_SCHEMA,
     Userid,
 };
@@ -33,32 +33,24 @@ pub const JOB_ID_SCHEMA: Schema = StringSchema::new("Job ID.")
     .schema();
 
 pub const SYNC_SCHEDULE_SCHEMA: Schema = StringSchema::new("Run sync job at specified schedule.")
-    .format(&ApiStringFormat::VerifyFn(
-        proxmox_time::verify_calendar_event,
-    ))
+    .format(&CALENDAR_EVENT_FORMAT)
     .type_text("<calendar-event>")
     .schema();
 
 pub const GC_SCHEDULE_SCHEMA: Schema =
     StringSchema::new("Run garbage collection job at specified schedule.")
-        .format(&ApiStringFormat::VerifyFn(
-            proxmox_time::verify_calendar_event,
-        ))
+        .format(&CALENDAR_EVENT_FORMAT)
         .type_text("<calendar-event>")
         .schema();
 
 pub const PRUNE_SCHEDULE_SCHEMA: Schema = StringSchema::new("Run prune job at specified schedule.")
-    .format(&ApiStringFormat::VerifyFn(
-        proxmox_time::verify_calendar_event,
-    ))
+    .format(&CALENDAR_EVENT_FORMAT)
     .type_text("<calendar-event>")
     .schema();
 
 pub const VERIFICATION_SCHEDULE_SCHEMA: Schema =
     StringSchema::new("Run verify job at specified schedule.")
-        .format(&ApiStringFormat::VerifyFn(
-            proxmox_time::verify_calendar_event,
-        ))
+        .format(&CALENDAR_EVENT_FORMAT)
         .type_text("<calendar-event>")
         .schema();
 
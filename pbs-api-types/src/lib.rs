@@ -188,6 +188,10 @@ pub const OPENSSL_CIPHERS_TLS_FORMAT: ApiStringFormat =
 pub const DAILY_DURATION_FORMAT: ApiStringFormat =
     ApiStringFormat::VerifyFn(|s| parse_daily_duration(s).map(drop));
 
+/// Format for systemd-like calendar events (`OnCalendar=`), for example `mon..fri 08..18:00`.
+pub const CALENDAR_EVENT_FORMAT: ApiStringFormat =
+    ApiStringFormat::VerifyFn(proxmox_time::verify_calendar_event);
+
 pub const SEARCH_DOMAIN_SCHEMA: Schema =
     StringSchema::new("Search domain for host-name lookup.").schema();
 
@@ -1,6 +1,7 @@
 use anyhow::{Error, bail};
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
+use std::fmt;
 
 #[cfg(feature = "enum-fallback")]
 use proxmox_fixed_string::FixedString;
@@ -9,6 +10,7 @@ use proxmox_schema::{ApiStringFormat, Schema, StringSchema, api, const_regex};
 
 const_regex! {
     pub MAINTENANCE_MESSAGE_REGEX = r"^[[:^cntrl:]]*$";
+    pub MAINTENANCE_REASON_CODE_REGEX = r"^[a-z0-9][a-z0-9_-]*$";
 }
 
 pub const MAINTENANCE_MESSAGE_FORMAT: ApiStringFormat =
@@ -20,7 +22,18 @@ pub const MAINTENANCE_MESSAGE_SCHEMA: Schema =
         .max_length(64)
         .schema();
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub const MAINTENANCE_REASON_CODE_FORMAT: ApiStringFormat =
+    ApiStringFormat::Pattern(&MAINTENANCE_REASON_CODE_REGEX);
+
+pub const MAINTENANCE_REASON_CODE_SCHEMA: Schema =
+    StringSchema::new("Machine readable code for the reason of the maintenance.")
+        .format(&MAINTENANCE_REASON_CODE_FORMAT)
+        .max_length(32)
+        .schema();
+
+#[api(string_enum: true)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
 /// Operation requirements, used when checking for maintenance mode.
 pub enum Operation {
     /// for any read operation like backup restore or RRD metric collection
@@ -35,7 +48,12 @@ pub enum Operation {
     // GarbageCollect or Delete?
 }
 
-#[api]
+impl Operation {
+    /// All operations, e.g. for checking which of them are allowed in a maintenance mode.
+    pub const ALL: [Operation; 3] = [Operation::Read, Operation::Write, Operation::Lookup];
+}
+
+#[api(string_enum: true)]
 #[derive(Copy, Clone, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 /// Maintenance type.
@@ -58,8 +76,6 @@ pub enum MaintenanceType {
     #[serde(untagged)]
     UnknownEnumValue(FixedString),
 }
-serde_plain::derive_display_from_serialize!(MaintenanceType);
-serde_plain::derive_fromstr_from_deserialize!(MaintenanceType);
 
 #[api(
     properties: {
@@ -69,12 +85,27 @@ serde_plain::derive_fromstr_from_deserialize!(MaintenanceType);
         message: {
             optional: true,
             schema: MAINTENANCE_MESSAGE_SCHEMA,
-        }
+        },
+        "reason-code": {
+            optional: true,
+            schema: MAINTENANCE_REASON_CODE_SCHEMA,
+        },
+        from: {
+            optional: true,
+            minimum: 0,
+        },
+        until: {
+            optional: true,
+            minimum: 0,
+        },
     },
     default_key: "type",
 )]
 #[derive(Deserialize, Serialize)]
+#[non_exhaustive]
 /// Maintenance mode
+///
+/// Use [`MaintenanceMode::new`] and the `with_*` methods to build one.
 pub struct MaintenanceMode {
     /// Type of maintenance ("read-only" or "offline").
     #[serde(rename = "type")]
@@ -83,36 +114,492 @@ pub struct MaintenanceMode {
     /// Reason for maintenance.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
+
+    /// Stable code for the reason of the maintenance, for monitoring to key off instead of the
+    /// free text `message`.
+    #[serde(rename = "reason-code", skip_serializing_if = "Option::is_none")]
+    pub reason_code: Option<String>,
+
+    /// Start of the maintenance window (epoch). Without it, the window starts immediately.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<i64>,
+
+    /// End of the maintenance window (epoch). Without it, the window is open ended.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<i64>,
 }
 
 impl MaintenanceMode {
-    /// Used for deciding whether the datastore is cleared from the internal cache
+    /// Create a maintenance mode which is in effect immediately and without an end.
+    pub fn new(ty: MaintenanceType, message: Option<String>) -> Self {
+        Self {
+            ty,
+            message,
+            reason_code: None,
+            from: None,
+            until: None,
+        }
+    }
+
+    /// Builder-style method to set a reason code, see [`MaintenanceError::reason_code`].
+    pub fn with_reason_code(mut self, reason_code: String) -> Self {
+        self.reason_code = Some(reason_code);
+        self
+    }
+
+    /// Builder-style method to restrict the maintenance mode to a time window.
+    ///
+    /// Fails if the window ends before it starts, see [`check_window`](Self::check_window).
+    pub fn with_window(mut self, from: Option<i64>, until: Option<i64>) -> Result<Self, Error> {
+        self.from = from;
+        self.until = until;
+        self.check_window()?;
+        Ok(self)
+    }
+
+    /// Check that the maintenance window is not empty, i.e. that it starts before it ends.
+    pub fn check_window(&self) -> Result<(), Error> {
+        match (self.from, self.until) {
+            (Some(from), Some(until)) if from >= until => {
+                bail!("maintenance window must start before it ends ({from} >= {until})")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Used for deciding whether the datastore is cleared from the internal cache right now, see
+    /// [`clear_from_cache_at`](Self::clear_from_cache_at).
     pub fn clear_from_cache(&self) -> bool {
-        self.ty == MaintenanceType::Offline
-            || self.ty == MaintenanceType::Delete
-            || self.ty == MaintenanceType::Unmount
+        self.clear_from_cache_at(proxmox_time::epoch_i64())
+    }
+
+    /// Used for deciding whether the datastore is cleared from the internal cache at the time
+    /// `now`. Outside of the maintenance window this is always false.
+    pub fn clear_from_cache_at(&self, now: i64) -> bool {
+        self.is_active_at(now)
+            && (self.ty == MaintenanceType::Offline
+                || self.ty == MaintenanceType::Delete
+                || self.ty == MaintenanceType::Unmount)
+    }
+
+    /// Check whether `operation` is permitted in this maintenance mode.
+    pub fn allows(&self, operation: Operation) -> bool {
+        match self.ty {
+            MaintenanceType::Delete => false,
+            _ if operation == Operation::Lookup => true,
+            MaintenanceType::Unmount | MaintenanceType::Offline | MaintenanceType::S3Refresh => {
+                false
+            }
+            MaintenanceType::ReadOnly => operation != Operation::Write,
+            #[cfg(feature = "enum-fallback")]
+            MaintenanceType::UnknownEnumValue(_) => true,
+        }
+    }
+
+    /// Get the set of operations which are currently permitted, e.g. for displaying them.
+    ///
+    /// Use [`check`](Self::check) to gate a single operation.
+    pub fn allowed_operations(&self) -> Vec<Operation> {
+        self.allowed_operations_at(proxmox_time::epoch_i64())
+    }
+
+    /// Get the set of operations which are permitted at the time `now`, consistent with
+    /// [`check_at`](Self::check_at).
+    pub fn allowed_operations_at(&self, now: i64) -> Vec<Operation> {
+        Operation::ALL
+            .into_iter()
+            .filter(|operation| self.check_at(*operation, now).is_ok())
+            .collect()
+    }
+
+    /// Check whether the maintenance window includes `now`. Without a window this is always
+    /// true.
+    pub fn is_active_at(&self, now: i64) -> bool {
+        self.from.is_none_or(|from| from <= now) && self.until.is_none_or(|until| now < until)
     }
 
+    /// Check whether `operation` is permitted right now, see [`check_at`](Self::check_at).
     pub fn check(&self, operation: Operation) -> Result<(), Error> {
-        if self.ty == MaintenanceType::Delete {
-            bail!("datastore is being deleted");
+        self.check_at(operation, proxmox_time::epoch_i64())
+    }
+
+    /// Check whether `operation` is permitted at the time `now`.
+    ///
+    /// Outside of the maintenance window all operations are permitted.
+    pub fn check_at(&self, operation: Operation, now: i64) -> Result<(), Error> {
+        if !self.is_active_at(now) || self.allows(operation) {
+            return Ok(());
         }
 
         let message = percent_encoding::percent_decode_str(self.message.as_deref().unwrap_or(""))
             .decode_utf8()
             .unwrap_or(Cow::Borrowed(""));
 
-        if Operation::Lookup == operation {
-            return Ok(());
-        } else if self.ty == MaintenanceType::Unmount {
-            bail!("datastore is being unmounted");
-        } else if self.ty == MaintenanceType::Offline {
-            bail!("offline maintenance mode: {}", message);
-        } else if self.ty == MaintenanceType::S3Refresh {
-            bail!("S3 refresh maintenance mode: {}", message);
-        } else if self.ty == MaintenanceType::ReadOnly && Operation::Write == operation {
-            bail!("read-only maintenance mode: {}", message);
+        let message = match self.ty {
+            MaintenanceType::Delete => "datastore is being deleted".to_string(),
+            MaintenanceType::Unmount => "datastore is being unmounted".to_string(),
+            MaintenanceType::Offline => format!("offline maintenance mode: {message}"),
+            MaintenanceType::S3Refresh => format!("S3 refresh maintenance mode: {message}"),
+            MaintenanceType::ReadOnly => format!("read-only maintenance mode: {message}"),
+            #[cfg(feature = "enum-fallback")]
+            MaintenanceType::UnknownEnumValue(_) => return Ok(()),
+        };
+
+        Err(MaintenanceError {
+            message,
+            reason_code: self.reason_code.clone(),
         }
-        Ok(())
+        .into())
+    }
+}
+
+/// Error returned by the maintenance mode checks when an operation is not permitted.
+///
+/// Use `downcast_ref` on the returned [`anyhow::Error`] to get to the reason code.
+#[derive(Debug)]
+pub struct MaintenanceError {
+    message: String,
+    reason_code: Option<String>,
+}
+
+impl MaintenanceError {
+    /// The reason code of the maintenance mode blocking the operation, if one was set.
+    pub fn reason_code(&self) -> Option<&str> {
+        self.reason_code.as_deref()
+    }
+}
+
+impl std::error::Error for MaintenanceError {}
+
+impl fmt::Display for MaintenanceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl MaintenanceType {
+    /// Rank maintenance types by how many operations they block, higher is more restrictive.
+    fn restrictiveness(&self) -> u8 {
+        match self {
+            MaintenanceType::Delete => 5,
+            MaintenanceType::Unmount => 4,
+            MaintenanceType::Offline => 3,
+            MaintenanceType::S3Refresh => 2,
+            MaintenanceType::ReadOnly => 1,
+            #[cfg(feature = "enum-fallback")]
+            MaintenanceType::UnknownEnumValue(_) => 0,
+        }
+    }
+}
+
+/// Multiple maintenance modes in effect at the same time, e.g. a read-only datastore which is
+/// also taken offline for some time.
+///
+/// Serialized as an array of [`MaintenanceMode`]s.
+#[derive(Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct MaintenanceModeSet {
+    modes: Vec<MaintenanceMode>,
+}
+
+impl MaintenanceModeSet {
+    /// Create an empty set, which permits all operations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a maintenance mode to the set.
+    pub fn add(&mut self, mode: MaintenanceMode) {
+        self.modes.push(mode);
+    }
+
+    /// Check whether the set contains no maintenance modes.
+    pub fn is_empty(&self) -> bool {
+        self.modes.is_empty()
+    }
+
+    /// Iterate over the contained maintenance modes.
+    pub fn iter(&self) -> impl Iterator<Item = &MaintenanceMode> {
+        self.modes.iter()
+    }
+
+    /// Get the most restrictive mode active at `now` which does not permit `operation`.
+    pub fn blocking_mode_at(&self, operation: Operation, now: i64) -> Option<&MaintenanceMode> {
+        self.modes
+            .iter()
+            .filter(|mode| mode.is_active_at(now) && !mode.allows(operation))
+            .max_by_key(|mode| mode.ty.restrictiveness())
+    }
+
+    /// Check whether `operation` is permitted right now, see [`check_at`](Self::check_at).
+    pub fn check(&self, operation: Operation) -> Result<(), Error> {
+        self.check_at(operation, proxmox_time::epoch_i64())
+    }
+
+    /// Check whether `operation` is permitted by all modes at the time `now`.
+    ///
+    /// If multiple modes block the operation, the error reports the most restrictive one.
+    pub fn check_at(&self, operation: Operation, now: i64) -> Result<(), Error> {
+        match self.blocking_mode_at(operation, now) {
+            Some(mode) => mode.check_at(operation, now),
+            None => Ok(()),
+        }
+    }
+}
+
+impl From<Vec<MaintenanceMode>> for MaintenanceModeSet {
+    fn from(modes: Vec<MaintenanceMode>) -> Self {
+        Self { modes }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        MaintenanceError, MaintenanceMode, MaintenanceModeSet, MaintenanceType, Operation,
+    };
+
+    #[test]
+    fn maintenance_type_string_round_trip() {
+        assert_eq!(MaintenanceType::ReadOnly.to_string(), "read-only");
+        assert_eq!(MaintenanceType::S3Refresh.to_string(), "s3-refresh");
+
+        for ty in [
+            MaintenanceType::ReadOnly,
+            MaintenanceType::Offline,
+            MaintenanceType::Delete,
+            MaintenanceType::Unmount,
+            MaintenanceType::S3Refresh,
+        ] {
+            let parsed: MaintenanceType = ty.to_string().parse().unwrap();
+            assert!(parsed == ty);
+            assert_eq!(serde_plain::to_string(&ty).unwrap(), ty.to_string());
+        }
+    }
+
+    #[test]
+    fn operation_string_round_trip() {
+        assert_eq!(Operation::Read.to_string(), "read");
+        assert_eq!(Operation::Write.to_string(), "write");
+        assert_eq!(Operation::Lookup.to_string(), "lookup");
+
+        for operation in Operation::ALL {
+            let parsed: Operation = operation.to_string().parse().unwrap();
+            assert_eq!(parsed, operation);
+            assert_eq!(
+                serde_plain::to_string(&operation).unwrap(),
+                operation.to_string()
+            );
+        }
+
+        assert!("Read".parse::<Operation>().is_err());
+        assert!("delete".parse::<Operation>().is_err());
+    }
+
+    #[test]
+    fn allowed_operations_match_check() {
+        for ty in [
+            MaintenanceType::ReadOnly,
+            MaintenanceType::Offline,
+            MaintenanceType::Delete,
+            MaintenanceType::Unmount,
+            MaintenanceType::S3Refresh,
+        ] {
+            let mode = MaintenanceMode::new(ty, Some("some%20reason".to_string()));
+            let allowed = mode.allowed_operations();
+            for operation in Operation::ALL {
+                assert_eq!(
+                    allowed.contains(&operation),
+                    mode.check(operation).is_ok(),
+                    "{ty} disagrees with check() for {operation:?}",
+                );
+            }
+        }
+
+        let read_only = MaintenanceMode::new(MaintenanceType::ReadOnly, None);
+        assert_eq!(
+            read_only.allowed_operations(),
+            [Operation::Read, Operation::Lookup]
+        );
+    }
+
+    #[test]
+    fn maintenance_window() {
+        let now = 1_700_000_000;
+        let window = |from: Option<i64>, until: Option<i64>| {
+            MaintenanceMode::new(MaintenanceType::Offline, None)
+                .with_window(from, until)
+                .unwrap()
+        };
+
+        // no window behaves as before
+        assert!(window(None, None).check_at(Operation::Read, now).is_err());
+        assert!(window(None, None).check_at(Operation::Lookup, now).is_ok());
+
+        // window in the past
+        let past = window(Some(now - 7200), Some(now - 3600));
+        assert!(!past.is_active_at(now));
+        assert!(past.check_at(Operation::Write, now).is_ok());
+
+        // window including now, the start is inclusive, the end exclusive
+        let present = window(Some(now - 3600), Some(now + 3600));
+        assert!(present.is_active_at(now));
+        assert!(present.check_at(Operation::Read, now).is_err());
+        assert!(present.check_at(Operation::Read, now - 3600).is_err());
+        assert!(present.check_at(Operation::Read, now + 3600).is_ok());
+
+        // window in the future
+        let future = window(Some(now + 3600), None);
+        assert!(!future.is_active_at(now));
+        assert!(future.check_at(Operation::Write, now).is_ok());
+        assert!(future.check_at(Operation::Write, now + 3600).is_err());
+
+        // open start
+        let until = window(None, Some(now + 60));
+        assert!(until.check_at(Operation::Read, now).is_err());
+        assert!(until.check_at(Operation::Read, now + 60).is_ok());
+    }
+
+    #[test]
+    fn maintenance_window_consistency() {
+        let now = 1_700_000_000;
+        let offline = |from, until| {
+            MaintenanceMode::new(MaintenanceType::Offline, None)
+                .with_window(from, until)
+                .unwrap()
+        };
+
+        // outside of the window everything is allowed and the datastore stays cached
+        for mode in [
+            offline(Some(now - 7200), Some(now - 3600)),
+            offline(Some(now + 3600), None),
+        ] {
+            assert_eq!(mode.allowed_operations_at(now), Operation::ALL);
+            assert!(!mode.clear_from_cache_at(now));
+        }
+
+        let mode = offline(Some(now + 3600), None);
+        assert_eq!(mode.allowed_operations_at(now + 3600), [Operation::Lookup]);
+        assert!(mode.clear_from_cache_at(now + 3600));
+
+        let mode = MaintenanceMode::new(MaintenanceType::ReadOnly, None)
+            .with_window(None, Some(now))
+            .unwrap();
+        assert!(!mode.clear_from_cache_at(now - 1));
+        assert_eq!(
+            mode.allowed_operations_at(now - 1),
+            [Operation::Read, Operation::Lookup]
+        );
+
+        // empty windows are rejected
+        let mode = MaintenanceMode::new(MaintenanceType::Offline, None);
+        assert!(mode.with_window(Some(now), Some(now)).is_err());
+        let mode = MaintenanceMode::new(MaintenanceType::Offline, None);
+        assert!(mode.with_window(Some(now), Some(now - 1)).is_err());
+    }
+
+    #[test]
+    fn maintenance_window_property_string() {
+        use proxmox_schema::ApiType;
+
+        let value = proxmox_schema::property_string::parse_with_schema(
+            "offline,from=100,until=200",
+            &MaintenanceMode::API_SCHEMA,
+        )
+        .unwrap();
+        let mode: MaintenanceMode = serde_json::from_value(value).unwrap();
+        assert_eq!(mode.from, Some(100));
+        assert_eq!(mode.until, Some(200));
+        assert!(mode.check_at(Operation::Read, 150).is_err());
+        assert!(mode.check_at(Operation::Read, 250).is_ok());
+    }
+
+    #[test]
+    fn maintenance_mode_set() {
+        let now = 1_700_000_000;
+        let mode = |ty, message: &str| MaintenanceMode::new(ty, Some(message.to_string()));
+
+        let set = MaintenanceModeSet::new();
+        assert!(set.is_empty());
+        assert!(set.check_at(Operation::Write, now).is_ok());
+
+        let mut set = MaintenanceModeSet::from(vec![
+            mode(MaintenanceType::ReadOnly, "gc"),
+            mode(MaintenanceType::Offline, "disk%20swap"),
+        ]);
+
+        // offline wins over read-only for a read operation
+        let err = set.check_at(Operation::Read, now).unwrap_err();
+        assert_eq!(err.to_string(), "offline maintenance mode: disk swap");
+        let err = set.check_at(Operation::Write, now).unwrap_err();
+        assert_eq!(err.to_string(), "offline maintenance mode: disk swap");
+        assert!(set.check_at(Operation::Lookup, now).is_ok());
+
+        // modes outside of their window are ignored
+        set.modes[1].until = Some(now);
+        assert!(set.check_at(Operation::Read, now).is_ok());
+        let err = set.check_at(Operation::Write, now).unwrap_err();
+        assert_eq!(err.to_string(), "read-only maintenance mode: gc");
+
+        set.add(mode(MaintenanceType::Delete, ""));
+        let err = set.check_at(Operation::Lookup, now).unwrap_err();
+        assert_eq!(err.to_string(), "datastore is being deleted");
+
+        let value = serde_json::to_value(&set).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!([
+                { "type": "read-only", "message": "gc" },
+                { "type": "offline", "message": "disk%20swap", "until": now },
+                { "type": "delete", "message": "" },
+            ])
+        );
+        let parsed: MaintenanceModeSet = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.iter().count(), 3);
+    }
+
+    #[test]
+    fn maintenance_reason_code() {
+        use proxmox_schema::ApiType;
+
+        let value = proxmox_schema::property_string::parse_with_schema(
+            "offline,message=disk%20swap,reason-code=hw-replace",
+            &MaintenanceMode::API_SCHEMA,
+        )
+        .unwrap();
+        let mode: MaintenanceMode = serde_json::from_value(value).unwrap();
+        assert_eq!(mode.reason_code.as_deref(), Some("hw-replace"));
+
+        let property_string = proxmox_schema::property_string::PropertyString::new(mode)
+            .to_property_string()
+            .unwrap();
+        assert!(property_string.contains("reason-code=hw-replace"));
+
+        let err = proxmox_schema::property_string::parse_with_schema::<serde_json::Value>(
+            "offline,reason-code=Not Valid",
+            &MaintenanceMode::API_SCHEMA,
+        );
+        assert!(err.is_err());
+
+        let mode = MaintenanceMode::new(MaintenanceType::ReadOnly, Some("gc".to_string()))
+            .with_reason_code("gc-running".to_string());
+        let err = mode.check_at(Operation::Write, 0).unwrap_err();
+        assert_eq!(err.to_string(), "read-only maintenance mode: gc");
+        let err = err.downcast_ref::<MaintenanceError>().unwrap();
+        assert_eq!(err.reason_code(), Some("gc-running"));
+
+        // unset reason codes are omitted
+        let mode = MaintenanceMode::new(MaintenanceType::ReadOnly, Some("gc".to_string()));
+        assert_eq!(
+            serde_json::to_value(&mode).unwrap(),
+            serde_json::json!({ "type": "read-only", "message": "gc" }),
+        );
+        let err = mode.check_at(Operation::Write, 0).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<MaintenanceError>()
+                .unwrap()
+                .reason_code(),
+            None
+        );
     }
 }
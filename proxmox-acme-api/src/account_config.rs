@@ -70,11 +70,11 @@ impl AccountData {
 
     pub fn client(&self) -> AcmeClient {
         let mut client = AcmeClient::new(self.directory_url.clone());
-        client.set_account(Account {
-            location: self.location.clone(),
-            private_key: self.key.clone(),
-            data: self.account.clone(),
-        });
+        client.set_account(Account::from_parts(
+            self.location.clone(),
+            self.key.clone(),
+            self.account.clone(),
+        ));
         client
     }
 }
@@ -3,8 +3,10 @@
 
 use std::collections::HashMap;
 use std::convert::TryFrom;
+use std::fmt;
+use std::time::Duration;
 
-use openssl::pkey::{PKey, Private};
+use openssl::pkey::{HasPublic, PKey, PKeyRef, Private};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -15,9 +17,49 @@ use crate::directory::Directory;
 use crate::jws::Jws;
 use crate::key::{Jwk, PublicKey};
 use crate::order::{NewOrder, Order, OrderData};
-use crate::request::Request;
+use crate::request::{DEFAULT_REQUEST_TIMEOUT, Request};
 use crate::types::{AccountData, AccountStatus, ExternalAccountBinding};
 
+/// The JWS signature algorithm used to sign requests of an account.
+///
+/// The algorithm is determined by the account's key type: RSA keys sign with `RS256`, P-256 EC
+/// keys with `ES256`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SignatureAlgorithm {
+    /// RSASSA-PKCS1-v1_5 using SHA-256.
+    Rs256,
+    /// ECDSA using P-256 and SHA-256.
+    Es256,
+}
+
+impl SignatureAlgorithm {
+    /// The `alg` value used in the JWS protected header.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SignatureAlgorithm::Rs256 => "RS256",
+            SignatureAlgorithm::Es256 => "ES256",
+        }
+    }
+
+    /// Get the algorithm to use for a key.
+    pub fn for_key<P: HasPublic>(key: &PKeyRef<P>) -> Result<Self, Error> {
+        Ok(Self::for_public_key(&PublicKey::try_from(key)?))
+    }
+
+    pub(crate) fn for_public_key(key: &PublicKey) -> Self {
+        match key {
+            PublicKey::Rsa(_) => SignatureAlgorithm::Rs256,
+            PublicKey::Ec(_) => SignatureAlgorithm::Es256,
+        }
+    }
+}
+
+impl fmt::Display for SignatureAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// An ACME Account.
 ///
 /// This contains the location URL, the account data and the private key for an account.
@@ -35,6 +77,10 @@ pub struct Account {
 
     /// base64url encoded PEM formatted private key.
     pub private_key: String,
+
+    /// Timeout for the requests created for this account, see [`Account::request_timeout`].
+    #[serde(skip)]
+    request_timeout: Option<Duration>,
 }
 
 impl Account {
@@ -44,9 +90,49 @@ impl Account {
             location,
             data,
             private_key,
+            request_timeout: None,
         }
     }
 
+    /// Build an account from the response to a `newAccount` request.
+    ///
+    /// The `location_header` is the account's URL (its "kid"), the `response_body` contains the
+    /// [`AccountData`] including the account's status.
+    pub fn from_response(
+        location_header: String,
+        response_body: &[u8],
+        private_key: String,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            location: location_header,
+            data: serde_json::from_slice(response_body)
+                .map_err(|err| Error::BadAccountData(err.to_string()))?,
+            private_key,
+            request_timeout: None,
+        })
+    }
+
+    /// Builder-style method to set the timeout for requests created for this account.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// The timeout for requests created for this account, see [`Request::suggested_timeout`].
+    ///
+    /// This is [`DEFAULT_REQUEST_TIMEOUT`] unless set via [`with_request_timeout`].
+    ///
+    /// [`with_request_timeout`]: Account::with_request_timeout
+    pub fn request_timeout(&self) -> Duration {
+        self.request_timeout.unwrap_or(DEFAULT_REQUEST_TIMEOUT)
+    }
+
+    /// Get the signature algorithm used for this account's requests.
+    pub fn algorithm(&self) -> Result<SignatureAlgorithm, Error> {
+        let key = PKey::private_key_from_pem(self.private_key.as_bytes())?;
+        SignatureAlgorithm::for_key(&key)
+    }
+
     /// Builds an [`AccountCreator`]. This handles creation of the private key and account data as
     /// well as handling the response sent by the server for the registration request.
     pub fn creator() -> AccountCreator {
@@ -86,9 +172,10 @@ impl Account {
             content_type: crate::request::JSON_CONTENT_TYPE,
             body,
             expected: &[crate::http_status::CREATED],
+            timeout: self.request_timeout(),
         };
 
-        Ok(NewOrder::new(request))
+        Ok(NewOrder::new(request, order.identifiers.clone()))
     }
 
     /// Prepare a "POST-as-GET" request to fetch data. Low level helper.
@@ -108,6 +195,7 @@ impl Account {
             content_type: crate::request::JSON_CONTENT_TYPE,
             body,
             expected: &[crate::http_status::OK],
+            timeout: self.request_timeout(),
         })
     }
 
@@ -133,6 +221,7 @@ impl Account {
             content_type: crate::request::JSON_CONTENT_TYPE,
             body,
             expected: &[crate::http_status::OK],
+            timeout: self.request_timeout(),
         })
     }
 
@@ -158,6 +247,7 @@ impl Account {
             content_type: crate::request::JSON_CONTENT_TYPE,
             body,
             expected: &[crate::http_status::OK],
+            timeout: self.request_timeout(),
         })
     }
 
@@ -168,6 +258,17 @@ impl Account {
         Ok(format!("{token}.{thumbprint}"))
     }
 
+    /// Get the content to serve for an http-01 token. This is the plain key authorization value,
+    /// it must be served at [`http_01_well_known_path`](Account::http_01_well_known_path).
+    pub fn http_01_key_authorization(&self, token: &str) -> Result<String, Error> {
+        self.key_authorization(token)
+    }
+
+    /// Get the path at which the key authorization for an http-01 token must be served.
+    pub fn http_01_well_known_path(token: &str) -> String {
+        format!("/.well-known/acme-challenge/{token}")
+    }
+
     /// Get the TXT field value for a dns-01 token. This is the base64url encoded sha256 digest of
     /// the key authorization value.
     pub fn dns_01_txt_value(&self, token: &str) -> Result<String, Error> {
@@ -187,6 +288,40 @@ impl Account {
         self.post_request(&self.location, nonce, data)
     }
 
+    /// Prepare a request to change the account's key to `new_key` (RFC 8555 section 7.3.5).
+    ///
+    /// The request contains a JWS signed by the new key nested in a JWS signed by the current
+    /// key. Once the server accepted the request, the account's `private_key` must be replaced
+    /// with the new key, otherwise the account becomes unusable.
+    pub fn change_key(
+        &self,
+        new_key: &PKeyRef<Private>,
+        directory: &Directory,
+        nonce: &str,
+    ) -> Result<Request, Error> {
+        let key = PKey::private_key_from_pem(self.private_key.as_bytes())?;
+        if key.public_eq(new_key) {
+            return Err(Error::Custom(
+                "new account key must differ from the current key".to_string(),
+            ));
+        }
+
+        let url = directory
+            .key_change_url()
+            .ok_or_else(|| Error::Custom("no 'keyChange' URL specified by provider".to_string()))?;
+
+        let inner = Jws::new_nested(
+            new_key,
+            url.to_owned(),
+            &serde_json::json!({
+                "account": self.location,
+                "oldKey": Jwk::try_from(&*key)?,
+            }),
+        )?;
+
+        self.post_request(url, nonce, &inner)
+    }
+
     /// Prepare a request to deactivate this account.
     pub fn deactivate_account_request<T: Serialize>(&self, nonce: &str) -> Result<Request, Error> {
         self.post_request_raw_payload(
@@ -297,7 +432,9 @@ pub struct AccountCreator {
     contact: Vec<String>,
     terms_of_service_agreed: bool,
     key: Option<PKey<Private>>,
+    algorithm: Option<SignatureAlgorithm>,
     eab_credentials: Option<(String, PKey<Private>)>,
+    request_timeout: Option<Duration>,
 }
 
 impl AccountCreator {
@@ -307,7 +444,7 @@ impl AccountCreator {
         self
     }
 
-    /// Append a contact string.
+    /// Append a contact string. This must be a `mailto:` URI.
     pub fn contact(mut self, contact: String) -> Self {
         self.contact.push(contact);
         self
@@ -326,12 +463,18 @@ impl AccountCreator {
     }
 
     /// Set the EAB credentials for the account registration
+    ///
+    /// The `hmac_key` is expected to be base64url encoded as provided by the CA, regular base64 is
+    /// accepted as well.
     pub fn set_eab_credentials(mut self, kid: String, hmac_key: String) -> Result<Self, Error> {
         let hmac_key = if hmac_key.contains('+') || hmac_key.contains('/') {
             proxmox_base64::decode(hmac_key)?
         } else {
             b64u::decode(&hmac_key)?
         };
+        if hmac_key.is_empty() {
+            return Err(Error::Custom("empty EAB HMAC key".to_string()));
+        }
         let hmac_key = PKey::hmac(&hmac_key)?;
         self.eab_credentials = Some((kid, hmac_key));
         Ok(self)
@@ -358,6 +501,37 @@ impl AccountCreator {
         self
     }
 
+    /// Require a specific signature algorithm for the account.
+    ///
+    /// Some CAs only accept certain algorithms. Since the algorithm is determined by the key type,
+    /// creating the request fails if the key does not match the algorithm.
+    pub fn algorithm(mut self, algorithm: SignatureAlgorithm) -> Self {
+        self.algorithm = Some(algorithm);
+        self
+    }
+
+    /// Set the timeout for the requests created for this account, this is also used by the
+    /// resulting [`Account`].
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    pub(crate) fn timeout(&self) -> Duration {
+        self.request_timeout.unwrap_or(DEFAULT_REQUEST_TIMEOUT)
+    }
+
+    /// Generate a new key suitable for the specified signature algorithm.
+    ///
+    /// This generates a 4096 bit key for `RS256` and a P-256 key for `ES256`.
+    pub fn generate_key(self, algorithm: SignatureAlgorithm) -> Result<Self, Error> {
+        let this = match algorithm {
+            SignatureAlgorithm::Rs256 => self.generate_rsa_key(4096)?,
+            SignatureAlgorithm::Es256 => self.generate_ec_key()?,
+        };
+        Ok(this.algorithm(algorithm))
+    }
+
     /// Prepare a HTTP request to create this account.
     ///
     /// Changes to the user data made after this will have no effect on the account generated with
@@ -366,6 +540,20 @@ impl AccountCreator {
     /// [`response`](AccountCreator::response()) will render the account unusable!
     pub fn request(&self, directory: &Directory, nonce: &str) -> Result<Request, Error> {
         let key = self.key.as_deref().ok_or(Error::MissingKey)?;
+        match self.algorithm {
+            Some(algorithm) if SignatureAlgorithm::for_key(key)? != algorithm => {
+                return Err(Error::AlgorithmMismatch(algorithm));
+            }
+            _ => (),
+        }
+
+        for contact in &self.contact {
+            match contact.strip_prefix("mailto:") {
+                Some(address) if !address.is_empty() => (),
+                _ => return Err(Error::InvalidContactInfo(contact.clone())),
+            }
+        }
+
         let url = directory.new_account_url().ok_or_else(|| {
             Error::Custom("no 'newAccount' URL specified by provider".to_string())
         })?;
@@ -406,6 +594,7 @@ impl AccountCreator {
             content_type: crate::request::JSON_CONTENT_TYPE,
             body,
             expected: &[crate::http_status::CREATED],
+            timeout: self.timeout(),
         })
     }
 
@@ -421,11 +610,10 @@ impl AccountCreator {
             Error::Custom("PEM key contained illegal non-utf-8 characters".to_string())
         })?;
 
+        let account = Account::from_response(location_header, response_body, private_key)?;
         Ok(Account {
-            location: location_header,
-            data: serde_json::from_slice(response_body)
-                .map_err(|err| Error::BadAccountData(err.to_string()))?,
-            private_key,
+            request_timeout: self.request_timeout,
+            ..account
         })
     }
 }
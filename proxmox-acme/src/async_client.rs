@@ -1,5 +1,7 @@
 //! Async HTTP Client implementation for the ACME protocol.
 
+use std::time::Duration;
+
 use anyhow::format_err;
 use bytes::Bytes;
 use http_body_util::BodyExt;
@@ -115,6 +117,7 @@ impl AcmeClient {
                 &self.directory_url,
                 &mut self.directory,
                 &mut self.nonce,
+                account.timeout(),
             )
             .await?;
             let request = account.request(directory, nonce)?;
@@ -150,6 +153,7 @@ impl AcmeClient {
                 &self.directory_url,
                 &mut self.directory,
                 &mut self.nonce,
+                account.request_timeout(),
             )
             .await?;
 
@@ -191,6 +195,7 @@ impl AcmeClient {
                 &self.directory_url,
                 &mut self.directory,
                 &mut self.nonce,
+                account.request_timeout(),
             )
             .await?;
 
@@ -226,6 +231,7 @@ impl AcmeClient {
                 &self.directory_url,
                 &mut self.directory,
                 &mut self.nonce,
+                account.request_timeout(),
             )
             .await?;
 
@@ -255,6 +261,7 @@ impl AcmeClient {
                 &self.directory_url,
                 &mut self.directory,
                 &mut self.nonce,
+                account.request_timeout(),
             )
             .await?;
 
@@ -323,6 +330,7 @@ impl AcmeClient {
                 &self.directory_url,
                 &mut self.directory,
                 &mut self.nonce,
+                account.request_timeout(),
             )
             .await?;
 
@@ -335,6 +343,14 @@ impl AcmeClient {
         }
     }
 
+    /// The timeout for requests not created by the account itself, like fetching the directory.
+    fn request_timeout(&self) -> Duration {
+        self.account
+            .as_ref()
+            .map(Account::request_timeout)
+            .unwrap_or(crate::DEFAULT_REQUEST_TIMEOUT)
+    }
+
     fn need_account(account: &Option<Account>) -> Result<&Account, anyhow::Error> {
         account
             .as_ref()
@@ -394,18 +410,26 @@ impl AcmeClient {
         }
         .map_err(|err| Error::Custom(format!("failed to create http request: {err}")))?;
 
-        let response = http_client
-            .request(http_request)
-            .await
-            .map_err(|err| Error::Custom(err.to_string()))?;
-        let (parts, body) = response.into_parts();
+        let timeout = request.timeout;
+        let (parts, body) = tokio::time::timeout(timeout, async {
+            let response = http_client
+                .request(http_request)
+                .await
+                .map_err(|err| Error::Custom(err.to_string()))?;
+            let (parts, body) = response.into_parts();
+
+            let body = body
+                .collect()
+                .await
+                .map_err(|err| Error::Custom(format!("failed to retrieve response body: {err}")))?
+                .to_bytes();
+
+            Ok::<_, Error>((parts, body))
+        })
+        .await
+        .map_err(|_| Error::Custom(format!("request timed out after {timeout:?}")))??;
 
         let status = parts.status.as_u16();
-        let body = body
-            .collect()
-            .await
-            .map_err(|err| Error::Custom(format!("failed to retrieve response body: {err}")))?
-            .to_bytes();
 
         let got_nonce = if let Some(new_nonce) = parts.headers.get(crate::REPLAY_NONCE) {
             let new_nonce = new_nonce.to_str().map_err(|err| {
@@ -471,11 +495,13 @@ impl AcmeClient {
 
     /// Get the Directory information.
     pub async fn directory(&mut self) -> Result<&Directory, Error> {
+        let timeout = self.request_timeout();
         Ok(Self::get_directory(
             &mut self.http_client,
             &self.directory_url,
             &mut self.directory,
             &mut self.nonce,
+            timeout,
         )
         .await?
         .0)
@@ -486,6 +512,7 @@ impl AcmeClient {
         directory_url: &str,
         directory: &'a mut Option<Directory>,
         nonce: &'b mut Option<String>,
+        timeout: Duration,
     ) -> Result<(&'a Directory, Option<&'b str>), Error> {
         if let Some(d) = directory {
             return Ok((d, nonce.as_deref()));
@@ -499,6 +526,7 @@ impl AcmeClient {
                 content_type: "",
                 body: String::new(),
                 expected: &[crate::http_status::OK],
+                timeout,
             },
             nonce,
         )
@@ -519,13 +547,14 @@ impl AcmeClient {
         directory_url: &str,
         directory: &'a mut Option<Directory>,
         nonce: &'b mut Option<String>,
+        timeout: Duration,
     ) -> Result<(&'a Directory, &'b str), Error> {
         // this let construct is a lifetime workaround:
-        let _ = Self::get_directory(http_client, directory_url, directory, nonce).await?;
+        let _ = Self::get_directory(http_client, directory_url, directory, nonce, timeout).await?;
         let dir = directory.as_ref().unwrap(); // the above fails if it couldn't fill this option
         if nonce.is_none() {
             // this is also a lifetime issue...
-            let _ = Self::get_nonce(http_client, nonce, dir.new_nonce_url()).await?;
+            let _ = Self::get_nonce(http_client, nonce, dir.new_nonce_url(), timeout).await?;
         };
         Ok((dir, nonce.as_deref().unwrap()))
     }
@@ -542,6 +571,7 @@ impl AcmeClient {
         http_client: &mut Client,
         nonce: &'a mut Option<String>,
         new_nonce_url: &str,
+        timeout: Duration,
     ) -> Result<&'a str, Error> {
         let response = Self::execute(
             http_client,
@@ -551,6 +581,7 @@ impl AcmeClient {
                 content_type: "",
                 body: String::new(),
                 expected: &[crate::http_status::OK, crate::http_status::NO_CONTENT],
+                timeout,
             },
             nonce,
         )
@@ -1,6 +1,7 @@
 //! A blocking higher-level ACME client implementation using 'curl'.
 
 use std::io::Read;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
@@ -58,6 +59,12 @@ impl HttpResponse {
             .take()
             .ok_or_else(|| format_err!("missing Location header"))
     }
+
+    /// Get the URL of the first link with the relation type `rel`. Borrowing shortcut to
+    /// [`Headers::find_link`].
+    pub fn find_link(&self, rel: &str) -> Option<&str> {
+        self.headers.find_link(rel)
+    }
 }
 
 /// Contains headers from the HTTP response which are relevant parts of the Acme API.
@@ -69,9 +76,24 @@ pub struct Headers {
     /// The 'Location' header usually encodes the URL where an account or order can be queried from
     /// after they were created.
     pub location: Option<String>,
+
+    /// The `(url, rel)` pairs of all 'Link' headers, as parsed by
+    /// [`parse_link_header`](crate::parse_link_header).
+    pub links: Vec<(String, String)>,
+
     nonce: Option<String>,
 }
 
+impl Headers {
+    /// Get the URL of the first link with the relation type `rel`, e.g. `"up"` or `"index"`.
+    pub fn find_link(&self, rel: &str) -> Option<&str> {
+        self.links
+            .iter()
+            .find(|(_, link_rel)| link_rel.eq_ignore_ascii_case(rel))
+            .map(|(url, _)| url.as_str())
+    }
+}
+
 struct Inner {
     agent: Option<ureq::Agent>,
     nonce: Option<String>,
@@ -119,6 +141,7 @@ impl Inner {
         method: &[u8],
         url: &str,
         request_body: Option<(&str, &[u8])>, // content-type and body
+        timeout: Duration,
     ) -> Result<HttpResponse, Error> {
         let agent = self.agent()?;
         let req = match method {
@@ -129,15 +152,25 @@ impl Inner {
         };
 
         let response = if let Some((content_type, body)) = request_body {
+            let req = req
+                .header("Content-Type", content_type)
+                .body(body)
+                .map_err(|err| format_err!("error building http request: {err:#}"))?;
             agent.run(
-                req.header("Content-Type", content_type)
-                    .body(body)
-                    .map_err(|err| format_err!("error building http request: {err:#}"))?,
+                agent
+                    .configure_request(req)
+                    .timeout_global(Some(timeout))
+                    .build(),
             )
         } else {
+            let req = req
+                .body(ureq::SendBody::none())
+                .map_err(|err| format_err!("error building http request: {err:#}"))?;
             agent.run(
-                req.body(ureq::SendBody::none())
-                    .map_err(|err| format_err!("error building http request: {err:#}"))?,
+                agent
+                    .configure_request(req)
+                    .timeout_global(Some(timeout))
+                    .build(),
             )
         }
         .map_err(|err| format_err!("http request failed: {err:#}"))?;
@@ -152,6 +185,14 @@ impl Inner {
             );
         }
 
+        for value in response.headers().get_all(crate::LINK) {
+            headers.links.extend(crate::parse_link_header(
+                value
+                    .to_str()
+                    .map_err(|_| format_err!("unexpected binary data in link header"))?,
+            ));
+        }
+
         if let Some(value) = response.headers().get(crate::REPLAY_NONCE) {
             headers.nonce = Some(
                 value
@@ -192,7 +233,12 @@ impl Inner {
         };
 
         let mut response = self
-            .execute(request.method.as_bytes(), &request.url, body)
+            .execute(
+                request.method.as_bytes(),
+                &request.url,
+                body,
+                request.timeout,
+            )
             .map_err({
                 // borrow fixup:
                 let method = &request.method;
@@ -249,10 +295,12 @@ impl Inner {
     }
 
     /// Update the Nonce.
-    fn new_nonce(&mut self, new_nonce_url: &str) -> Result<(), Error> {
-        let mut response = self.execute(b"HEAD", new_nonce_url, None).map_err(|err| {
-            Error::InvalidApi(format!("failed to get HEAD of newNonce URL: {err}"))
-        })?;
+    fn new_nonce(&mut self, new_nonce_url: &str, timeout: Duration) -> Result<(), Error> {
+        let mut response = self
+            .execute(b"HEAD", new_nonce_url, None, timeout)
+            .map_err(|err| {
+                Error::InvalidApi(format!("failed to get HEAD of newNonce URL: {err}"))
+            })?;
 
         if !response.is_success() {
             bail!("HEAD on newNonce URL returned error");
@@ -264,9 +312,9 @@ impl Inner {
     }
 
     /// Make sure a nonce is available without forcing renewal.
-    fn nonce(&mut self, new_nonce_url: &str) -> Result<&str, Error> {
+    fn nonce(&mut self, new_nonce_url: &str, timeout: Duration) -> Result<&str, Error> {
         if self.nonce.is_none() {
-            self.new_nonce(new_nonce_url)?;
+            self.new_nonce(new_nonce_url, timeout)?;
         }
         self.nonce
             .as_deref()
@@ -309,7 +357,21 @@ impl Client {
 
     /// Get the Directory information.
     pub fn directory(&mut self) -> Result<&Directory, Error> {
-        Self::get_directory(&mut self.inner, &mut self.directory, &self.directory_url)
+        let timeout = self.request_timeout();
+        Self::get_directory(
+            &mut self.inner,
+            &mut self.directory,
+            &self.directory_url,
+            timeout,
+        )
+    }
+
+    /// The timeout for requests not created by the account itself, like fetching the directory.
+    fn request_timeout(&self) -> Duration {
+        self.account
+            .as_ref()
+            .map(Account::request_timeout)
+            .unwrap_or(crate::DEFAULT_REQUEST_TIMEOUT)
     }
 
     /// Get the Directory information.
@@ -317,13 +379,14 @@ impl Client {
         inner: &'_ mut Inner,
         directory: &'a mut Option<Directory>,
         directory_url: &str,
+        timeout: Duration,
     ) -> Result<&'a Directory, Error> {
         if let Some(d) = directory {
             return Ok(d);
         }
 
         let response = inner
-            .execute(b"GET", directory_url, None)
+            .execute(b"GET", directory_url, None, timeout)
             .map_err(|err| Error::InvalidApi(format!("failed to get directory info: {err}")))?;
 
         if !response.is_success() {
@@ -358,20 +421,29 @@ impl Client {
     /// specification should include a new valid nonce in its headers anyway).
     pub fn new_nonce(&mut self) -> Result<(), Error> {
         let was_none = self.inner.nonce.is_none();
-        let directory =
-            Self::get_directory(&mut self.inner, &mut self.directory, &self.directory_url)?;
+        let timeout = self.request_timeout();
+        let directory = Self::get_directory(
+            &mut self.inner,
+            &mut self.directory,
+            &self.directory_url,
+            timeout,
+        )?;
         if was_none && self.inner.nonce.is_some() {
             // this was the first call and we already got a nonce from querying the directory
             return Ok(());
         }
 
         // otherwise actually call up to get a new nonce
-        self.inner.new_nonce(directory.new_nonce_url())
+        self.inner.new_nonce(directory.new_nonce_url(), timeout)
     }
 
     /// borrow helper
-    fn nonce<'a>(inner: &'a mut Inner, directory: &'_ Directory) -> Result<&'a str, Error> {
-        inner.nonce(directory.new_nonce_url())
+    fn nonce<'a>(
+        inner: &'a mut Inner,
+        directory: &'_ Directory,
+        timeout: Duration,
+    ) -> Result<&'a str, Error> {
+        inner.nonce(directory.new_nonce_url(), timeout)
     }
 
     /// Convenience method to create a new account with a list of ACME compatible contact strings
@@ -416,9 +488,13 @@ impl Client {
         let mut response = loop {
             retry.tick()?;
 
-            let directory =
-                Self::get_directory(&mut self.inner, &mut self.directory, &self.directory_url)?;
-            let nonce = Self::nonce(&mut self.inner, directory)?;
+            let directory = Self::get_directory(
+                &mut self.inner,
+                &mut self.directory,
+                &self.directory_url,
+                account.timeout(),
+            )?;
+            let nonce = Self::nonce(&mut self.inner, directory, account.timeout())?;
             let request = account.request(directory, nonce)?;
             match self.run_request(request) {
                 Ok(response) => break response,
@@ -449,9 +525,13 @@ impl Client {
         let mut retry = retry();
         let response = loop {
             retry.tick()?;
-            let directory =
-                Self::get_directory(&mut self.inner, &mut self.directory, &self.directory_url)?;
-            let nonce = Self::nonce(&mut self.inner, directory)?;
+            let directory = Self::get_directory(
+                &mut self.inner,
+                &mut self.directory,
+                &self.directory_url,
+                account.request_timeout(),
+            )?;
+            let nonce = Self::nonce(&mut self.inner, directory, account.request_timeout())?;
             let request = account.post_request(&account.location, nonce, data)?;
             let response = match self.inner.run_request(request) {
                 Ok(response) => response,
@@ -483,9 +563,13 @@ impl Client {
         loop {
             retry.tick()?;
 
-            let directory =
-                Self::get_directory(&mut self.inner, &mut self.directory, &self.directory_url)?;
-            let nonce = Self::nonce(&mut self.inner, directory)?;
+            let directory = Self::get_directory(
+                &mut self.inner,
+                &mut self.directory,
+                &self.directory_url,
+                account.request_timeout(),
+            )?;
+            let nonce = Self::nonce(&mut self.inner, directory, account.request_timeout())?;
             let mut new_order = account.new_order(&order, directory, nonce)?;
             let mut response = match self.inner.run_request(new_order.request.take().unwrap()) {
                 Ok(response) => response,
@@ -515,9 +599,13 @@ impl Client {
         loop {
             retry.tick()?;
 
-            let directory =
-                Self::get_directory(&mut self.inner, &mut self.directory, &self.directory_url)?;
-            let nonce = Self::nonce(&mut self.inner, directory)?;
+            let directory = Self::get_directory(
+                &mut self.inner,
+                &mut self.directory,
+                &self.directory_url,
+                account.request_timeout(),
+            )?;
+            let nonce = Self::nonce(&mut self.inner, directory, account.request_timeout())?;
             let request = account.get_request(url, nonce)?;
             match self.inner.run_request(request) {
                 Ok(response) => return Ok(response),
@@ -535,9 +623,13 @@ impl Client {
         loop {
             retry.tick()?;
 
-            let directory =
-                Self::get_directory(&mut self.inner, &mut self.directory, &self.directory_url)?;
-            let nonce = Self::nonce(&mut self.inner, directory)?;
+            let directory = Self::get_directory(
+                &mut self.inner,
+                &mut self.directory,
+                &self.directory_url,
+                account.request_timeout(),
+            )?;
+            let nonce = Self::nonce(&mut self.inner, directory, account.request_timeout())?;
             let request = account.post_request(url, nonce, data)?;
             match self.inner.run_request(request) {
                 Ok(response) => return Ok(response),
@@ -598,9 +690,13 @@ impl Client {
         loop {
             retry.tick()?;
 
-            let directory =
-                Self::get_directory(&mut self.inner, &mut self.directory, &self.directory_url)?;
-            let nonce = Self::nonce(&mut self.inner, directory)?;
+            let directory = Self::get_directory(
+                &mut self.inner,
+                &mut self.directory,
+                &self.directory_url,
+                account.request_timeout(),
+            )?;
+            let nonce = Self::nonce(&mut self.inner, directory, account.request_timeout())?;
             let request = revocation.request(directory, nonce)?;
             match self.inner.run_request(request) {
                 Ok(_response) => return Ok(()),
@@ -104,6 +104,10 @@ impl Directory {
         self.data.new_order.as_deref()
     }
 
+    pub(crate) fn key_change_url(&self) -> Option<&str> {
+        self.data.key_change.as_deref()
+    }
+
     /// Access to the in the Acme spec defined metadata structure.
     pub fn meta(&self) -> Option<&Meta> {
         self.data.meta.as_ref()
@@ -10,6 +10,12 @@ pub const BAD_NONCE: &str = "urn:ietf:params:acme:error:badNonce";
 /// The ACME error string for a "user action required" error.
 pub const USER_ACTION_REQUIRED: &str = "urn:ietf:params:acme:error:userActionRequired";
 
+/// The ACME error string for a "rate limited" error.
+pub const RATE_LIMITED: &str = "urn:ietf:params:acme:error:rateLimited";
+
+/// The ACME error string for an internal server error.
+pub const SERVER_INTERNAL: &str = "urn:ietf:params:acme:error:serverInternal";
+
 /// Error types returned by this crate.
 #[derive(Debug)]
 #[must_use = "unused errors have no effect"]
@@ -34,6 +40,9 @@ pub enum Error {
     /// Tried to create an `Account` without providing a single contact info.
     MissingContactInfo,
 
+    /// Tried to create an `Account` with a contact which is not a `mailto:` URI.
+    InvalidContactInfo(String),
+
     /// Tried to use an empty `Order`.
     EmptyOrder,
 
@@ -43,6 +52,9 @@ pub enum Error {
     /// A raw `openssl::PKey` or `openssl::EcKey` with an unsupported curve was passed.
     UnsupportedGroup,
 
+    /// The account key cannot be used with the requested signature algorithm.
+    AlgorithmMismatch(crate::account::SignatureAlgorithm),
+
     /// Failed to parse the account data returned by the API upon account creation.
     BadAccountData(String),
 
@@ -106,9 +118,21 @@ impl fmt::Display for Error {
             Error::UserActionRequired(err) => write!(f, "user action required: {err}"),
             Error::MissingKey => f.write_str("cannot build an account without a key"),
             Error::MissingContactInfo => f.write_str("account requires contact info"),
+            Error::InvalidContactInfo(contact) => {
+                write!(
+                    f,
+                    "invalid contact info '{contact}', expected a 'mailto:' URI"
+                )
+            }
             Error::EmptyOrder => f.write_str("cannot make an empty order"),
             Error::UnsupportedKeyType => f.write_str("unsupported key type"),
             Error::UnsupportedGroup => f.write_str("unsupported EC group"),
+            Error::AlgorithmMismatch(alg) => {
+                write!(
+                    f,
+                    "account key cannot be used with signature algorithm {alg}"
+                )
+            }
             Error::BadAccountData(err) => {
                 write!(f, "bad response to account query or creation: {err}")
             }
@@ -6,6 +6,7 @@ use openssl::sign::Signer;
 use serde::Serialize;
 
 use crate::Error;
+use crate::account::SignatureAlgorithm;
 use crate::b64u;
 use crate::key::{Jwk, PublicKey};
 
@@ -13,7 +14,8 @@ use crate::key::{Jwk, PublicKey};
 #[serde(rename_all = "camelCase")]
 pub struct Protected {
     alg: &'static str,
-    nonce: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nonce: Option<String>,
     url: String,
     #[serde(flatten)]
     key: KeyId,
@@ -65,6 +67,32 @@ impl Jws {
         url: String,
         nonce: String,
         payload: String,
+    ) -> Result<Self, Error> {
+        Self::new_signed(key, location, url, Some(nonce), payload)
+    }
+
+    /// Create a JWS without a nonce, identified by its `jwk`, as used for the inner JWS of a key
+    /// change request.
+    pub fn new_nested<P, T>(key: &PKeyRef<P>, url: String, payload: &T) -> Result<Self, Error>
+    where
+        P: HasPrivate,
+        T: Serialize,
+    {
+        Self::new_signed(
+            key,
+            None,
+            url,
+            None,
+            b64u::encode(serde_json::to_string(payload)?.as_bytes()),
+        )
+    }
+
+    fn new_signed<P: HasPrivate>(
+        key: &PKeyRef<P>,
+        location: Option<String>,
+        url: String,
+        nonce: Option<String>,
+        payload: String,
     ) -> Result<Self, Error> {
         let jwk = Jwk::try_from(key)?;
 
@@ -79,10 +107,11 @@ impl Jws {
             },
         };
 
-        let (digest, ec_order_bytes): (MessageDigest, usize) = match &pubkey {
-            PublicKey::Rsa(_) => (Self::prepare_rsa(key, &mut protected), 0),
-            PublicKey::Ec(_) => Self::prepare_ec(key, &mut protected),
-        };
+        let (digest, ec_order_bytes): (MessageDigest, usize) =
+            match SignatureAlgorithm::for_public_key(&pubkey) {
+                SignatureAlgorithm::Rs256 => (Self::prepare_rsa(key, &mut protected), 0),
+                SignatureAlgorithm::Es256 => Self::prepare_ec(key, &mut protected),
+            };
 
         let protected_data = b64u::encode(serde_json::to_string(&protected)?.as_bytes());
 
@@ -108,7 +137,7 @@ impl Jws {
     where
         P: HasPrivate,
     {
-        protected.alg = "RS256";
+        protected.alg = SignatureAlgorithm::Rs256.as_str();
         MessageDigest::sha256()
     }
 
@@ -118,7 +147,7 @@ impl Jws {
         P: HasPrivate,
     {
         // Note: if we support >256 bit keys we'll want to also support using ES512 here probably
-        protected.alg = "ES256";
+        protected.alg = SignatureAlgorithm::Es256.as_str();
         //  'r' and 's' are each 256 bit numbers:
         (MessageDigest::sha256(), 32)
     }
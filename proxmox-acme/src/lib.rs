@@ -18,6 +18,8 @@
 #[cfg(feature = "api-types")]
 pub mod types;
 
+mod link;
+
 #[cfg(feature = "impl")]
 mod b64u;
 #[cfg(feature = "impl")]
@@ -70,13 +72,17 @@ pub use order::Order;
 #[doc(inline)]
 pub use request::Request;
 
+pub use link::parse_link_header;
+
 // we don't inline these:
 #[cfg(feature = "impl")]
 pub use order::NewOrder;
 #[cfg(feature = "impl")]
-pub use request::ErrorResponse;
-#[cfg(feature = "impl")]
 pub(crate) use request::http_status;
+#[cfg(feature = "impl")]
+pub use request::{DEFAULT_REQUEST_TIMEOUT, ErrorResponse, is_retriable_error};
+#[cfg(feature = "impl")]
+pub use util::{generate_csr, split_pem_chain};
 
 /// Header name for nonces.
 pub const REPLAY_NONCE: &str = "Replay-Nonce";
@@ -84,6 +90,9 @@ pub const REPLAY_NONCE: &str = "Replay-Nonce";
 /// Header name for locations.
 pub const LOCATION: &str = "Location";
 
+/// Header name for links to related resources.
+pub const LINK: &str = "Link";
+
 #[cfg(feature = "client")]
 pub mod client;
 #[cfg(feature = "client")]
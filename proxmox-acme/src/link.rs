@@ -0,0 +1,143 @@
+//! Parsing of `Link` headers (RFC 8288).
+//!
+//! ACME servers use these to point to related resources, such as the `index` relation referencing
+//! the directory, or the `up` relation pointing from a certificate or authorization back to the
+//! order or account it belongs to.
+
+/// Parse the value of a `Link` header into a list of `(url, rel)` pairs.
+///
+/// A link with multiple space separated relation types produces one entry per type, links
+/// without a `rel` parameter and malformed entries are skipped. Multiple `Link` headers can be
+/// parsed individually or joined with a comma.
+///
+/// ```
+/// let links = proxmox_acme::parse_link_header(
+///     r#"<https://example.com/acme/directory>;rel="index", <https://example.com/acme/acct/1>; rel=up"#,
+/// );
+/// assert_eq!(
+///     links,
+///     [
+///         ("https://example.com/acme/directory".to_string(), "index".to_string()),
+///         ("https://example.com/acme/acct/1".to_string(), "up".to_string()),
+///     ]
+/// );
+/// ```
+pub fn parse_link_header(value: &str) -> Vec<(String, String)> {
+    let mut links = Vec::new();
+    let mut rest = value;
+
+    loop {
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+        if rest.is_empty() {
+            break;
+        }
+
+        let (link, remaining) = split_link(rest);
+        rest = remaining;
+
+        let Some((url, params)) = link.strip_prefix('<').and_then(|link| link.split_once('>'))
+        else {
+            continue;
+        };
+
+        for param in split_params(params) {
+            let Some((name, value)) = param.split_once('=') else {
+                continue;
+            };
+            if !name.trim().eq_ignore_ascii_case("rel") {
+                continue;
+            }
+
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value);
+            for rel in value.split_ascii_whitespace() {
+                links.push((url.to_string(), rel.to_ascii_lowercase()));
+            }
+
+            // only the first `rel` parameter is relevant
+            break;
+        }
+    }
+
+    links
+}
+
+/// Split off the first link of a header value at the first comma which is not part of the URL or
+/// a quoted string.
+fn split_link(value: &str) -> (&str, &str) {
+    let mut in_url = false;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' if !in_url => in_quotes = !in_quotes,
+            '<' if !in_quotes => in_url = true,
+            '>' if !in_quotes => in_url = false,
+            ',' if !in_url && !in_quotes => return (&value[..i], &value[i + 1..]),
+            _ => (),
+        }
+    }
+
+    (value, "")
+}
+
+/// Iterate over the `;` separated parameters of a single link, respecting quoted strings.
+fn split_params(params: &str) -> impl Iterator<Item = &str> {
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    params
+        .split(move |c| match c {
+            _ if escaped => {
+                escaped = false;
+                false
+            }
+            '\\' if in_quotes => {
+                escaped = true;
+                false
+            }
+            '"' => {
+                in_quotes = !in_quotes;
+                false
+            }
+            ';' => !in_quotes,
+            _ => false,
+        })
+        .map(str::trim)
+        .filter(|param| !param.is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_link_header;
+
+    #[test]
+    fn test_parse_link_header() {
+        let links = parse_link_header(concat!(
+            r#"<https://acme.example.com/acme/directory>;rel="index", "#,
+            r#"<https://acme.example.com/acme/order/1,2>; title="a; b, c"; rel="up alternate", "#,
+            r#"<https://acme.example.com/acme/no-rel>; title="ignored", "#,
+            r#"not-a-link; rel="up", "#,
+            r#"<https://acme.example.com/acme/cert/1/alt>;REL=Alternate"#,
+        ));
+
+        assert_eq!(
+            links,
+            [
+                ("https://acme.example.com/acme/directory", "index"),
+                ("https://acme.example.com/acme/order/1,2", "up"),
+                ("https://acme.example.com/acme/order/1,2", "alternate"),
+                ("https://acme.example.com/acme/cert/1/alt", "alternate"),
+            ]
+            .map(|(url, rel)| (url.to_string(), rel.to_string()))
+        );
+
+        assert!(parse_link_header("").is_empty());
+    }
+}
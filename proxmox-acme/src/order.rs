@@ -64,6 +64,15 @@ pub enum Identifier {
     Dns(String),
 }
 
+impl Identifier {
+    /// Domain names are compared case insensitively.
+    fn matches(&self, other: &Identifier) -> bool {
+        match (self, other) {
+            (Identifier::Dns(a), Identifier::Dns(b)) => a.eq_ignore_ascii_case(b),
+        }
+    }
+}
+
 /// This contains the order data sent to and received from the ACME server.
 ///
 /// This is typically filled with a set of domains and then issued as a new-order request via [`Account::new_order`](crate::Account::new_order).
@@ -81,6 +90,10 @@ pub struct OrderData {
     /// List of identifiers to order for the certificate.
     pub identifiers: Vec<Identifier>,
 
+    /// The certificate profile to use, if the ACME server supports profile selection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+
     /// An RFC3339 formatted time string. It is up to the user to choose a dev dependency for this
     /// shit.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -120,6 +133,38 @@ impl OrderData {
         self.identifiers.push(Identifier::Dns(domain));
         self
     }
+
+    /// Get the order's expiration date as epoch, if the server provided one.
+    ///
+    /// This can be used to decide whether a stale order should be abandoned in favor of a new one.
+    pub fn expires_epoch(&self) -> Result<Option<i64>, Error> {
+        self.expires
+            .as_deref()
+            .map(|expires| {
+                proxmox_time::parse_rfc3339(expires).map_err(|err| {
+                    Error::BadOrderData(format!("invalid expiration date '{expires}' - {err}"))
+                })
+            })
+            .transpose()
+    }
+
+    /// Builder-style method to select a certificate profile.
+    pub fn profile(mut self, name: &str) -> Self {
+        self.profile = Some(name.to_string());
+        self
+    }
+
+    /// Iterate over the domain names of the DNS identifiers in this order.
+    pub fn domains(&self) -> impl Iterator<Item = &str> {
+        self.identifiers.iter().map(|id| match id {
+            Identifier::Dns(domain) => domain.as_str(),
+        })
+    }
+
+    /// Get the domain names of the DNS identifiers in this order.
+    pub fn dns_names(&self) -> Vec<&str> {
+        self.domains().collect()
+    }
 }
 
 /// Represents an order for a new certificate. This combines the order's own location (URL) with
@@ -135,6 +180,17 @@ pub struct Order {
 }
 
 impl Order {
+    /// Build an order from its location and a response body containing its [`OrderData`].
+    ///
+    /// This can be used to rebuild an [`Order`] after re-fetching it from the server while
+    /// polling its status.
+    pub fn from_response(location: String, response_body: &[u8]) -> Result<Self, Error> {
+        let data: OrderData = serde_json::from_slice(response_body)
+            .map_err(|err| Error::BadOrderData(err.to_string()))?;
+
+        Ok(Order { location, data })
+    }
+
     /// Get an authorization URL (or `None` if the index is out of range).
     pub fn authorization(&self, index: usize) -> Option<&str> {
         Some(self.data.authorizations.get(index)?)
@@ -144,32 +200,275 @@ impl Order {
     pub fn authorization_len(&self) -> usize {
         self.data.authorizations.len()
     }
+
+    /// Determine what to do next with this order based on its status.
+    ///
+    /// A valid order without a certificate URL results in [`OrderAction::Wait`].
+    pub fn next_action(&self) -> OrderAction<'_> {
+        match self.data.status {
+            Status::New | Status::Pending => OrderAction::SolveAuthorizations(
+                self.data
+                    .authorizations
+                    .iter()
+                    .map(String::as_str)
+                    .collect(),
+            ),
+            Status::Ready => OrderAction::Finalize,
+            Status::Processing => OrderAction::Wait,
+            Status::Valid => match self.data.certificate.as_deref() {
+                Some(url) => OrderAction::Download(url),
+                None => OrderAction::Wait,
+            },
+            Status::Invalid => OrderAction::Failed(self.data.error.as_ref()),
+        }
+    }
+}
+
+/// The next step to take for an [`Order`], see [`Order::next_action`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum OrderAction<'a> {
+    /// The authorizations at these URLs need to be completed.
+    SolveAuthorizations(Vec<&'a str>),
+
+    /// All authorizations are done, the order needs to be finalized by sending the CSR.
+    Finalize,
+
+    /// The certificate has been issued and can be downloaded from this URL.
+    Download(&'a str),
+
+    /// The order failed, with the error reported by the ACME provider, if any.
+    Failed(Option<&'a Value>),
+
+    /// The ACME provider is still processing the order, it should be queried again later.
+    Wait,
 }
 
 /// Represents a new in-flight order creation.
 ///
 /// This is created via [`Account::new_order`](crate::Account::new_order()).
 pub struct NewOrder {
-    //order: OrderData,
+    /// The identifiers which were requested, used to verify the server's response.
+    identifiers: Vec<Identifier>,
+
     /// The request to execute to place the order. When creating a [`NewOrder`] via
     /// [`Account::new_order`](crate::Account::new_order) this is guaranteed to be `Some`.
     pub request: Option<Request>,
 }
 
 impl NewOrder {
-    pub(crate) fn new(request: Request) -> Self {
+    pub(crate) fn new(request: Request, identifiers: Vec<Identifier>) -> Self {
         Self {
-            //order,
+            identifiers,
             request: Some(request),
         }
     }
 
     /// Deal with the response we got from the server.
+    ///
+    /// This fails if the order returned by the server does not contain all of the requested
+    /// identifiers.
     pub fn response(self, location_header: String, response_body: &[u8]) -> Result<Order, Error> {
-        Ok(Order {
-            location: location_header,
-            data: serde_json::from_slice(response_body)
-                .map_err(|err| Error::BadOrderData(err.to_string()))?,
-        })
+        let order = Order::from_response(location_header, response_body)?;
+
+        for requested in &self.identifiers {
+            if !order
+                .data
+                .identifiers
+                .iter()
+                .any(|id| id.matches(requested))
+            {
+                return Err(Error::BadOrderData(format!(
+                    "order is missing the requested identifier {requested:?}"
+                )));
+            }
+        }
+
+        Ok(order)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Identifier, NewOrder, Order, OrderAction, OrderData, Status};
+
+    fn order(status: Status) -> Order {
+        Order {
+            location: "https://acme.example.com/acme/order/1".to_string(),
+            data: OrderData {
+                status,
+                authorizations: vec![
+                    "https://acme.example.com/acme/authz/1".to_string(),
+                    "https://acme.example.com/acme/authz/2".to_string(),
+                ],
+                ..OrderData::new()
+            },
+        }
+    }
+
+    #[test]
+    fn test_next_action() {
+        let authorizations = vec![
+            "https://acme.example.com/acme/authz/1",
+            "https://acme.example.com/acme/authz/2",
+        ];
+        assert_eq!(
+            order(Status::New).next_action(),
+            OrderAction::SolveAuthorizations(authorizations.clone())
+        );
+        assert_eq!(
+            order(Status::Pending).next_action(),
+            OrderAction::SolveAuthorizations(authorizations)
+        );
+        assert_eq!(order(Status::Ready).next_action(), OrderAction::Finalize);
+        assert_eq!(order(Status::Processing).next_action(), OrderAction::Wait);
+
+        let mut valid = order(Status::Valid);
+        assert_eq!(valid.next_action(), OrderAction::Wait);
+        valid.data.certificate = Some("https://acme.example.com/acme/cert/1".to_string());
+        assert_eq!(
+            valid.next_action(),
+            OrderAction::Download("https://acme.example.com/acme/cert/1")
+        );
+
+        let mut invalid = order(Status::Invalid);
+        assert_eq!(invalid.next_action(), OrderAction::Failed(None));
+        let error = serde_json::json!({ "type": "urn:ietf:params:acme:error:rejectedIdentifier" });
+        invalid.data.error = Some(error.clone());
+        assert_eq!(invalid.next_action(), OrderAction::Failed(Some(&error)));
+    }
+
+    #[test]
+    fn test_profile_serialization() {
+        let data = OrderData::new().domain("example.com".to_string());
+        let value = serde_json::to_value(&data).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "identifiers": [{ "type": "dns", "value": "example.com" }],
+            })
+        );
+
+        let data = data.profile("shortlived");
+        let value = serde_json::to_value(&data).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "identifiers": [{ "type": "dns", "value": "example.com" }],
+                "profile": "shortlived",
+            })
+        );
+
+        let data: OrderData = serde_json::from_value(serde_json::json!({
+            "status": "pending",
+            "identifiers": [{ "type": "dns", "value": "example.com" }],
+            "profile": "shortlived",
+            "authorizations": [],
+        }))
+        .unwrap();
+        assert_eq!(data.profile.as_deref(), Some("shortlived"));
+    }
+
+    #[test]
+    fn test_domains() {
+        assert!(OrderData::new().dns_names().is_empty());
+
+        let data = OrderData::new()
+            .domain("example.com".to_string())
+            .domain("*.example.com".to_string())
+            .domain("www.example.org".to_string());
+        assert_eq!(
+            data.dns_names(),
+            ["example.com", "*.example.com", "www.example.org"]
+        );
+        assert_eq!(data.domains().nth(1), Some("*.example.com"));
+    }
+
+    #[test]
+    fn test_new_order_response_identifiers() {
+        let new_order = || {
+            let request = crate::Request {
+                url: "https://acme.example.com/acme/new-order".to_string(),
+                method: "POST",
+                content_type: crate::request::JSON_CONTENT_TYPE,
+                body: String::new(),
+                expected: &[crate::http_status::CREATED],
+                timeout: crate::DEFAULT_REQUEST_TIMEOUT,
+            };
+            NewOrder::new(
+                request,
+                vec![
+                    Identifier::Dns("example.com".to_string()),
+                    Identifier::Dns("www.example.com".to_string()),
+                ],
+            )
+        };
+        let response = |identifiers: serde_json::Value| {
+            serde_json::to_vec(&serde_json::json!({
+                "status": "pending",
+                "identifiers": identifiers,
+                "authorizations": [],
+            }))
+            .unwrap()
+        };
+        let location = "https://acme.example.com/acme/order/1".to_string();
+
+        let body = response(serde_json::json!([
+            { "type": "dns", "value": "WWW.example.com" },
+            { "type": "dns", "value": "example.com" },
+        ]));
+        let order = new_order().response(location.clone(), &body).unwrap();
+        assert_eq!(order.data.identifiers.len(), 2);
+
+        let body = response(serde_json::json!([
+            { "type": "dns", "value": "example.com" },
+            { "type": "dns", "value": "evil.example.com" },
+        ]));
+        assert!(new_order().response(location.clone(), &body).is_err());
+
+        let body = response(serde_json::json!([]));
+        assert!(new_order().response(location, &body).is_err());
+    }
+
+    #[test]
+    fn test_order_from_response() {
+        let body = serde_json::to_vec(&serde_json::json!({
+            "status": "valid",
+            "expires": "2026-10-22T00:00:00Z",
+            "identifiers": [{ "type": "dns", "value": "example.com" }],
+            "authorizations": ["https://acme.example.com/acme/authz/1"],
+            "finalize": "https://acme.example.com/acme/order/1/finalize",
+            "certificate": "https://acme.example.com/acme/cert/1",
+        }))
+        .unwrap();
+        let location = "https://acme.example.com/acme/order/1".to_string();
+
+        let order = Order::from_response(location.clone(), &body).unwrap();
+        assert_eq!(order.location, location);
+        assert!(order.data.status.is_valid());
+        assert_eq!(
+            order.data.identifiers,
+            [Identifier::Dns("example.com".to_string())]
+        );
+        assert_eq!(
+            order.next_action(),
+            OrderAction::Download("https://acme.example.com/acme/cert/1")
+        );
+
+        assert!(Order::from_response(location, b"{\"status\": \"bogus\"}").is_err());
+    }
+
+    #[test]
+    fn test_expires_epoch() {
+        let mut data = OrderData::new();
+        assert_eq!(data.expires_epoch().unwrap(), None);
+
+        data.expires = Some("2026-10-22T00:00:00Z".to_string());
+        assert_eq!(data.expires_epoch().unwrap(), Some(1_792_627_200));
+
+        data.expires = Some("2026-10-22T02:00:00+02:00".to_string());
+        assert_eq!(data.expires_epoch().unwrap(), Some(1_792_627_200));
+
+        data.expires = Some("next week".to_string());
+        assert!(data.expires_epoch().is_err());
     }
 }
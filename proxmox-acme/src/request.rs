@@ -1,7 +1,12 @@
+use std::time::Duration;
+
 use serde::Deserialize;
 
 pub(crate) const JSON_CONTENT_TYPE: &str = "application/jose+json";
 
+/// The timeout suggested for requests if none was configured.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// A request which should be performed on the ACME provider.
 pub struct Request {
     /// The complete URL to send the request to.
@@ -18,6 +23,40 @@ pub struct Request {
 
     /// The set of HTTP status codes that indicate a successful response from an ACME provider.
     pub expected: &'static [u16],
+
+    /// The timeout the HTTP client should apply to this request, see
+    /// [`Request::suggested_timeout`].
+    pub(crate) timeout: Duration,
+}
+
+impl Request {
+    /// The complete URL to send the request to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The HTTP method name to use, e.g. `"POST"`.
+    pub fn method(&self) -> &str {
+        self.method
+    }
+
+    /// The `Content-Type` header to pass along, usually `application/jose+json`.
+    pub fn content_type(&self) -> &str {
+        self.content_type
+    }
+
+    /// The raw request body, which may be empty.
+    pub fn body(&self) -> &[u8] {
+        self.body.as_bytes()
+    }
+
+    /// The timeout the HTTP client should apply to this request.
+    ///
+    /// This is [`DEFAULT_REQUEST_TIMEOUT`] unless a different timeout was configured on the
+    /// [`Account`](crate::Account) or [`AccountCreator`](crate::account::AccountCreator).
+    pub fn suggested_timeout(&self) -> Duration {
+        self.timeout
+    }
 }
 
 /// Common HTTP status codes used in ACME responses.
@@ -49,3 +88,46 @@ pub struct ErrorResponse {
     /// Additional json data containing information as to why the error occurred.
     pub subproblems: Option<serde_json::Value>,
 }
+
+/// Check whether a request which failed with `problem` may succeed when retried later.
+///
+/// This is the case for `badNonce` (retry with the fresh nonce), `rateLimited` (retry after the
+/// limit has passed) and `serverInternal` errors. Everything else, like `unauthorized` or
+/// `malformed`, is considered a permanent failure.
+pub fn is_retriable_error(problem: &ErrorResponse) -> bool {
+    use crate::error::{BAD_NONCE, RATE_LIMITED, SERVER_INTERNAL};
+
+    matches!(
+        problem.ty.as_str(),
+        BAD_NONCE | RATE_LIMITED | SERVER_INTERNAL
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ErrorResponse, is_retriable_error};
+
+    #[test]
+    fn test_is_retriable_error() {
+        let problem = |ty: &str| -> ErrorResponse {
+            serde_json::from_value(serde_json::json!({
+                "type": ty,
+                "detail": "some detail",
+            }))
+            .unwrap()
+        };
+
+        for (ty, retriable) in [
+            ("urn:ietf:params:acme:error:badNonce", true),
+            ("urn:ietf:params:acme:error:rateLimited", true),
+            ("urn:ietf:params:acme:error:serverInternal", true),
+            ("urn:ietf:params:acme:error:unauthorized", false),
+            ("urn:ietf:params:acme:error:malformed", false),
+            ("urn:ietf:params:acme:error:rejectedIdentifier", false),
+            ("urn:ietf:params:acme:error:userActionRequired", false),
+            ("about:blank", false),
+        ] {
+            assert_eq!(is_retriable_error(&problem(ty)), retriable, "{ty}");
+        }
+    }
+}
@@ -4,9 +4,9 @@ use std::collections::HashMap;
 
 use openssl::hash::MessageDigest;
 use openssl::nid::Nid;
-use openssl::pkey::PKey;
+use openssl::pkey::{PKey, PKeyRef, Private};
 use openssl::rsa::Rsa;
-use openssl::x509::{self, X509Name, X509Req};
+use openssl::x509::{self, X509, X509Name, X509NameRef, X509Req};
 
 use crate::Error;
 
@@ -49,37 +49,215 @@ impl Csr {
         }
         let name = name.build();
 
-        let mut csr = X509Req::builder()?;
-        csr.set_subject_name(&name)?;
-        csr.set_pubkey(&private_key)?;
-
-        let context = csr.x509v3_context(None);
-        let mut ext = openssl::stack::Stack::new()?;
-        ext.push(x509::extension::BasicConstraints::new().build()?)?;
-        ext.push(
-            x509::extension::KeyUsage::new()
-                .digital_signature()
-                .key_encipherment()
-                .build()?,
-        )?;
-        ext.push(
-            x509::extension::ExtendedKeyUsage::new()
-                .server_auth()
-                .client_auth()
-                .build()?,
-        )?;
-        let mut san = x509::extension::SubjectAlternativeName::new();
-        for dns in identifiers {
-            san.dns(dns.as_ref());
-        }
-        ext.push({ san }.build(&context)?)?;
-        csr.add_extensions(&ext)?;
-
-        csr.sign(&private_key, MessageDigest::sha256())?;
+        let data = build_csr(&private_key, &name, identifiers)?;
 
         Ok(Self {
-            data: csr.build().to_der()?,
+            data,
             private_key_pem,
         })
     }
 }
+
+/// Generate a DER encoded CSR for an existing private key.
+///
+/// The `sans` should be the domains of the order's identifiers, they are added as DNS names of
+/// the subject alternative name extension. The `common_name` is used for the subject, unless it
+/// is empty, in which case the subject stays empty as well.
+pub fn generate_csr(
+    key: &PKeyRef<Private>,
+    common_name: &str,
+    sans: &[&str],
+) -> Result<Vec<u8>, Error> {
+    if sans.is_empty() {
+        return Err(Error::Csr("cannot generate empty CSR".to_string()));
+    }
+
+    let mut name = X509Name::builder()?;
+    if !common_name.is_empty() {
+        name.append_entry_by_nid(Nid::COMMONNAME, common_name)?;
+    }
+    let name = name.build();
+
+    build_csr(key, &name, sans)
+}
+
+fn build_csr(
+    key: &PKeyRef<Private>,
+    name: &X509NameRef,
+    identifiers: &[impl AsRef<str>],
+) -> Result<Vec<u8>, Error> {
+    let mut csr = X509Req::builder()?;
+    csr.set_subject_name(name)?;
+    csr.set_pubkey(key)?;
+
+    let context = csr.x509v3_context(None);
+    let mut ext = openssl::stack::Stack::new()?;
+    ext.push(x509::extension::BasicConstraints::new().build()?)?;
+    ext.push(
+        x509::extension::KeyUsage::new()
+            .digital_signature()
+            .key_encipherment()
+            .build()?,
+    )?;
+    ext.push(
+        x509::extension::ExtendedKeyUsage::new()
+            .server_auth()
+            .client_auth()
+            .build()?,
+    )?;
+    let mut san = x509::extension::SubjectAlternativeName::new();
+    for dns in identifiers {
+        san.dns(dns.as_ref());
+    }
+    ext.push({ san }.build(&context)?)?;
+    csr.add_extensions(&ext)?;
+
+    csr.sign(key, MessageDigest::sha256())?;
+
+    Ok(csr.build().to_der()?)
+}
+
+/// Split a PEM encoded certificate chain, as downloaded from an order's `certificate` URL, into
+/// its individual PEM blocks.
+///
+/// The certificates are returned in their original order, which means the leaf certificate comes
+/// first, followed by the intermediates. Each block is checked to be a valid certificate, and any
+/// data between the blocks is rejected, as RFC 8555 does not allow explanatory text there.
+pub fn split_pem_chain(pem: &[u8]) -> Result<Vec<Vec<u8>>, Error> {
+    const BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+    const END: &str = "-----END CERTIFICATE-----";
+
+    let mut rest = std::str::from_utf8(pem)
+        .map_err(|_| Error::InvalidApi("certificate chain is not valid PEM data".to_string()))?;
+
+    let mut chain = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+
+        let index = chain.len();
+        if !rest.starts_with(BEGIN) {
+            return Err(Error::InvalidApi(format!(
+                "unexpected data in certificate chain before certificate {index}"
+            )));
+        }
+
+        let end = rest[BEGIN.len()..]
+            .find(END)
+            .map(|end| BEGIN.len() + end + END.len())
+            .ok_or_else(|| {
+                Error::InvalidApi(format!("unterminated certificate {index} in chain"))
+            })?;
+
+        let block = format!("{}\n", &rest[..end]);
+        X509::from_pem(block.as_bytes()).map_err(|err| {
+            Error::InvalidApi(format!("invalid certificate {index} in chain - {err}"))
+        })?;
+
+        chain.push(block.into_bytes());
+        rest = &rest[end..];
+    }
+
+    if chain.is_empty() {
+        return Err(Error::InvalidApi(
+            "no certificates in certificate chain".to_string(),
+        ));
+    }
+
+    Ok(chain)
+}
+
+#[cfg(test)]
+mod test {
+    use openssl::asn1::Asn1Time;
+    use openssl::ec::{EcGroup, EcKey};
+    use openssl::hash::MessageDigest;
+    use openssl::nid::Nid;
+    use openssl::pkey::PKey;
+    use openssl::x509::{X509, X509Name, X509Req};
+
+    use super::{generate_csr, split_pem_chain};
+
+    fn certificate(common_name: &str) -> Vec<u8> {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+
+        let mut name = X509Name::builder().unwrap();
+        name.append_entry_by_nid(Nid::COMMONNAME, common_name)
+            .unwrap();
+        let name = name.build();
+
+        let mut cert = X509::builder().unwrap();
+        cert.set_subject_name(&name).unwrap();
+        cert.set_issuer_name(&name).unwrap();
+        cert.set_pubkey(&key).unwrap();
+        cert.set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        cert.set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        cert.sign(&key, MessageDigest::sha256()).unwrap();
+        cert.build().to_pem().unwrap()
+    }
+
+    #[test]
+    fn test_split_pem_chain() {
+        let leaf = certificate("example.com");
+        let intermediate = certificate("Example Intermediate CA");
+        let chain = [leaf.as_slice(), b"\n", intermediate.as_slice()].concat();
+
+        let split = split_pem_chain(&chain).unwrap();
+        // leaf first
+        assert_eq!(split, [leaf.clone(), intermediate]);
+
+        // a single certificate without a trailing newline
+        let single = split_pem_chain(leaf.trim_ascii_end()).unwrap();
+        assert_eq!(single, [leaf]);
+    }
+
+    #[test]
+    fn test_split_pem_chain_malformed() {
+        let leaf = certificate("example.com");
+
+        assert!(split_pem_chain(b"").is_err());
+        assert!(split_pem_chain(b"not a certificate").is_err());
+
+        // explanatory text in between
+        let chain = [leaf.as_slice(), b"intermediate:\n", leaf.as_slice()].concat();
+        assert!(split_pem_chain(&chain).is_err());
+
+        // truncated second certificate
+        let chain = [leaf.as_slice(), &leaf[..leaf.len() / 2]].concat();
+        assert!(split_pem_chain(&chain).is_err());
+
+        // corrupted base64 data
+        let mut corrupted = leaf.clone();
+        corrupted[40] = b'!';
+        assert!(split_pem_chain(&corrupted).is_err());
+    }
+
+    #[test]
+    fn test_generate_csr() {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+
+        let der = generate_csr(&key, "example.com", &["example.com", "www.example.com"]).unwrap();
+        let csr = X509Req::from_der(&der).unwrap();
+
+        assert!(csr.verify(&key).unwrap());
+        assert!(csr.public_key().unwrap().public_eq(&key));
+
+        let common_name = csr
+            .subject_name()
+            .entries_by_nid(Nid::COMMONNAME)
+            .next()
+            .unwrap();
+        assert_eq!(common_name.data().as_slice(), b"example.com");
+
+        let text = String::from_utf8(csr.to_text().unwrap()).unwrap();
+        assert!(text.contains("DNS:example.com, DNS:www.example.com"));
+
+        assert!(generate_csr(&key, "example.com", &[]).is_err());
+    }
+}
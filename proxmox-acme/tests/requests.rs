@@ -0,0 +1,327 @@
+use std::time::Duration;
+
+use proxmox_acme::directory::{Directory, DirectoryData};
+use proxmox_acme::order::OrderData;
+use proxmox_acme::types::AccountStatus;
+use proxmox_acme::{Account, DEFAULT_REQUEST_TIMEOUT, Error, Request};
+
+const NONCE: &str = "test-nonce";
+const LOCATION: &str = "https://acme.example.com/acme/acct/1";
+
+fn test_directory() -> Directory {
+    let data: DirectoryData = serde_json::from_value(serde_json::json!({
+        "newNonce": "https://acme.example.com/acme/new-nonce",
+        "newAccount": "https://acme.example.com/acme/new-acct",
+        "newOrder": "https://acme.example.com/acme/new-order",
+        "revokeCert": "https://acme.example.com/acme/revoke-cert",
+        "keyChange": "https://acme.example.com/acme/key-change",
+    }))
+    .unwrap();
+    Directory::from_parts("https://acme.example.com/directory".to_string(), data)
+}
+
+fn test_account(directory: &Directory) -> Account {
+    let creator = Account::creator().generate_ec_key().unwrap();
+    creator.request(directory, NONCE).unwrap();
+    creator
+        .response(LOCATION.to_string(), br#"{"status":"valid"}"#)
+        .unwrap()
+}
+
+fn decode_json(data: &str) -> serde_json::Value {
+    serde_json::from_slice(&proxmox_base64::url::decode_no_pad(data).unwrap()).unwrap()
+}
+
+fn jws_payload(request: &Request) -> serde_json::Value {
+    let jws: serde_json::Value = serde_json::from_slice(request.body()).unwrap();
+    decode_json(jws["payload"].as_str().unwrap())
+}
+
+#[test]
+fn new_order_request() {
+    let directory = test_directory();
+    let account = test_account(&directory);
+
+    let order = OrderData::new().domain("example.com".to_string());
+    let new_order = account.new_order(&order, &directory, NONCE).unwrap();
+    let request = new_order.request.as_ref().unwrap();
+
+    assert_eq!(request.url(), "https://acme.example.com/acme/new-order");
+    assert_eq!(request.method(), "POST");
+    assert_eq!(request.content_type(), "application/jose+json");
+
+    let payload = jws_payload(request);
+    assert_eq!(
+        payload["identifiers"],
+        serde_json::json!([{ "type": "dns", "value": "example.com" }]),
+    );
+}
+
+#[test]
+fn new_account_request() {
+    let directory = test_directory();
+    let creator = Account::creator()
+        .email("admin@example.com".to_string())
+        .contact("mailto:ops@example.com".to_string())
+        .agree_to_tos(true)
+        .generate_ec_key()
+        .unwrap();
+
+    let request = creator.request(&directory, NONCE).unwrap();
+    assert_eq!(request.url(), "https://acme.example.com/acme/new-acct");
+    assert_eq!(request.method(), "POST");
+    assert_eq!(request.content_type(), "application/jose+json");
+
+    let payload = jws_payload(&request);
+    assert_eq!(
+        payload["contact"],
+        serde_json::json!(["mailto:admin@example.com", "mailto:ops@example.com"]),
+    );
+    assert_eq!(payload["termsOfServiceAgreed"], true);
+
+    let account = creator
+        .response(LOCATION.to_string(), br#"{"status":"valid"}"#)
+        .unwrap();
+    assert_eq!(account.location, LOCATION);
+    assert!(account.data.status == AccountStatus::Valid);
+}
+
+#[test]
+fn new_account_invalid_contact() {
+    let directory = test_directory();
+    for contact in ["admin@example.com", "mailto:", "tel:+1234"] {
+        let creator = Account::creator()
+            .contact(contact.to_string())
+            .generate_ec_key()
+            .unwrap();
+        match creator.request(&directory, NONCE) {
+            Err(Error::InvalidContactInfo(invalid)) => assert_eq!(invalid, contact),
+            Err(err) => panic!("unexpected error for contact '{contact}': {err}"),
+            Ok(_) => panic!("contact '{contact}' was not rejected"),
+        }
+    }
+}
+
+#[test]
+fn new_account_request_with_eab() {
+    const EAB_KID: &str = "kid-1";
+    let hmac_key = b"a not so secret hmac key for tests";
+    let encoded_key = proxmox_base64::url::encode_no_pad(hmac_key);
+
+    let directory = test_directory();
+    let creator = Account::creator()
+        .email("admin@example.com".to_string())
+        .agree_to_tos(true)
+        .set_eab_credentials(EAB_KID.to_string(), encoded_key)
+        .unwrap()
+        .generate_ec_key()
+        .unwrap();
+
+    let request = creator.request(&directory, NONCE).unwrap();
+    let jws: serde_json::Value = serde_json::from_slice(request.body()).unwrap();
+    let jws_protected = decode_json(jws["protected"].as_str().unwrap());
+
+    let eab = &jws_payload(&request)["externalAccountBinding"];
+    let protected = eab["protected"].as_str().unwrap();
+    let payload = eab["payload"].as_str().unwrap();
+
+    let eab_protected = decode_json(protected);
+    assert_eq!(eab_protected["alg"], "HS256");
+    assert_eq!(eab_protected["kid"], EAB_KID);
+    assert_eq!(
+        eab_protected["url"],
+        "https://acme.example.com/acme/new-acct"
+    );
+
+    // the EAB payload is the account's public key
+    assert_eq!(decode_json(payload), jws_protected["jwk"]);
+
+    let key = openssl::pkey::PKey::hmac(hmac_key).unwrap();
+    let mut signer =
+        openssl::sign::Signer::new(openssl::hash::MessageDigest::sha256(), &key).unwrap();
+    signer
+        .update(format!("{protected}.{payload}").as_bytes())
+        .unwrap();
+    let signature = proxmox_base64::url::encode_no_pad(signer.sign_to_vec().unwrap());
+    assert_eq!(eab["signature"], signature);
+}
+
+#[test]
+fn invalid_eab_hmac_key() {
+    for key in ["not base64url!", ""] {
+        assert!(
+            Account::creator()
+                .set_eab_credentials("kid-1".to_string(), key.to_string())
+                .is_err(),
+            "EAB HMAC key '{key}' was not rejected",
+        );
+    }
+}
+
+#[test]
+fn http_01_key_authorization() {
+    use openssl::bn::{BigNum, BigNumContext};
+    use openssl::pkey::PKey;
+
+    let account = test_account(&test_directory());
+
+    // compute the RFC 7638 thumbprint of the account's P-256 key by hand
+    let key = PKey::private_key_from_pem(account.private_key.as_bytes()).unwrap();
+    let key = key.ec_key().unwrap();
+    let mut x = BigNum::new().unwrap();
+    let mut y = BigNum::new().unwrap();
+    let mut ctx = BigNumContext::new().unwrap();
+    key.public_key()
+        .affine_coordinates(key.group(), &mut x, &mut y, &mut ctx)
+        .unwrap();
+    let jwk = format!(
+        r#"{{"crv":"P-256","kty":"EC","x":"{}","y":"{}"}}"#,
+        proxmox_base64::url::encode_no_pad(x.to_vec_padded(32).unwrap()),
+        proxmox_base64::url::encode_no_pad(y.to_vec_padded(32).unwrap()),
+    );
+    let thumbprint = proxmox_base64::url::encode_no_pad(openssl::sha::sha256(jwk.as_bytes()));
+
+    let token = "evaGxfADs6pSRb2LAv9IZf17Dt3juxGJ-PCt92wr-oA";
+    let key_authorization = account.http_01_key_authorization(token).unwrap();
+    assert_eq!(key_authorization, format!("{token}.{thumbprint}"));
+    assert_eq!(key_authorization, account.key_authorization(token).unwrap());
+
+    // dns-01 uses the hashed value instead
+    assert_eq!(
+        account.dns_01_txt_value(token).unwrap(),
+        proxmox_base64::url::encode_no_pad(openssl::sha::sha256(key_authorization.as_bytes())),
+    );
+
+    assert_eq!(
+        Account::http_01_well_known_path(token),
+        format!("/.well-known/acme-challenge/{token}"),
+    );
+}
+
+fn jws_protected(request: &Request) -> serde_json::Value {
+    let jws: serde_json::Value = serde_json::from_slice(request.body()).unwrap();
+    decode_json(jws["protected"].as_str().unwrap())
+}
+
+#[test]
+fn signature_algorithm() {
+    use proxmox_acme::account::SignatureAlgorithm;
+
+    let directory = test_directory();
+
+    let creator = Account::creator()
+        .generate_ec_key()
+        .unwrap()
+        .algorithm(SignatureAlgorithm::Es256);
+    let request = creator.request(&directory, NONCE).unwrap();
+    assert_eq!(jws_protected(&request)["alg"], "ES256");
+    let account = creator
+        .response(LOCATION.to_string(), br#"{"status":"valid"}"#)
+        .unwrap();
+    assert_eq!(account.algorithm().unwrap(), SignatureAlgorithm::Es256);
+    let request = account.get_request(LOCATION, NONCE).unwrap();
+    assert_eq!(jws_protected(&request)["alg"], "ES256");
+
+    let creator = Account::creator()
+        .generate_rsa_key(2048)
+        .unwrap()
+        .algorithm(SignatureAlgorithm::Rs256);
+    let request = creator.request(&directory, NONCE).unwrap();
+    assert_eq!(jws_protected(&request)["alg"], "RS256");
+    let account = creator
+        .response(LOCATION.to_string(), br#"{"status":"valid"}"#)
+        .unwrap();
+    assert_eq!(account.algorithm().unwrap(), SignatureAlgorithm::Rs256);
+    let request = account.get_request(LOCATION, NONCE).unwrap();
+    assert_eq!(jws_protected(&request)["alg"], "RS256");
+
+    // the key type must match the requested algorithm
+    let creator = Account::creator()
+        .generate_ec_key()
+        .unwrap()
+        .algorithm(SignatureAlgorithm::Rs256);
+    assert!(matches!(
+        creator.request(&directory, NONCE),
+        Err(Error::AlgorithmMismatch(SignatureAlgorithm::Rs256)),
+    ));
+}
+
+#[test]
+fn change_key_request() {
+    let directory = test_directory();
+    let account = test_account(&directory);
+
+    let new_key = openssl::pkey::PKey::from_ec_key(
+        openssl::ec::EcKey::generate(
+            openssl::ec::EcGroup::from_curve_name(openssl::nid::Nid::X9_62_PRIME256V1)
+                .unwrap()
+                .as_ref(),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    let request = account.change_key(&new_key, &directory, NONCE).unwrap();
+    assert_eq!(request.url(), "https://acme.example.com/acme/key-change");
+    assert_eq!(request.method(), "POST");
+
+    // the outer JWS is signed by the current key and identifies the account
+    let outer = jws_protected(&request);
+    assert_eq!(outer["kid"], LOCATION);
+    assert_eq!(outer["nonce"], NONCE);
+    assert_eq!(outer["url"], "https://acme.example.com/acme/key-change");
+
+    // the inner JWS is signed by the new key and carries no nonce
+    let inner = jws_payload(&request);
+    let inner_protected = decode_json(inner["protected"].as_str().unwrap());
+    assert_eq!(inner_protected["url"], outer["url"]);
+    assert!(inner_protected.get("nonce").is_none());
+    assert!(inner_protected.get("kid").is_none());
+    assert_eq!(inner_protected["jwk"]["kty"], "EC");
+    assert!(inner["signature"].is_string());
+
+    let inner_payload = decode_json(inner["payload"].as_str().unwrap());
+    assert_eq!(inner_payload["account"], LOCATION);
+    assert_eq!(inner_payload["oldKey"]["kty"], "EC");
+    assert_ne!(inner_payload["oldKey"], inner_protected["jwk"]);
+
+    // changing to the same key is rejected
+    let old_key =
+        openssl::pkey::PKey::private_key_from_pem(account.private_key.as_bytes()).unwrap();
+    assert!(account.change_key(&old_key, &directory, NONCE).is_err());
+}
+
+#[test]
+fn request_timeout() {
+    let directory = test_directory();
+    let account = test_account(&directory);
+
+    assert_eq!(account.request_timeout(), DEFAULT_REQUEST_TIMEOUT);
+    let request = account.get_request(LOCATION, NONCE).unwrap();
+    assert_eq!(request.suggested_timeout(), DEFAULT_REQUEST_TIMEOUT);
+
+    let account = account.with_request_timeout(Duration::from_secs(5));
+    assert_eq!(account.request_timeout(), Duration::from_secs(5));
+    let request = account.get_request(LOCATION, NONCE).unwrap();
+    assert_eq!(request.suggested_timeout(), Duration::from_secs(5));
+    let order = OrderData::new().domain("example.com".to_string());
+    let order = account.new_order(&order, &directory, NONCE).unwrap();
+    assert_eq!(
+        order.request.unwrap().suggested_timeout(),
+        Duration::from_secs(5)
+    );
+
+    // the timeout is carried over from the account creator
+    let creator = Account::creator()
+        .generate_ec_key()
+        .unwrap()
+        .request_timeout(Duration::from_secs(90));
+    let request = creator.request(&directory, NONCE).unwrap();
+    assert_eq!(request.suggested_timeout(), Duration::from_secs(90));
+    let account = creator
+        .response(LOCATION.to_string(), br#"{"status":"valid"}"#)
+        .unwrap();
+    assert_eq!(account.request_timeout(), Duration::from_secs(90));
+    let request = account.get_request(LOCATION, NONCE).unwrap();
+    assert_eq!(request.suggested_timeout(), Duration::from_secs(90));
+}
@@ -14,6 +14,10 @@ use crate::util::{self, FieldName, JSONObject, JSONValue, Maybe};
 
 /// Enums, provided they're simple enums, simply get an enum string schema attached to them.
 pub fn handle_enum(attribs: JSONObject, enum_ty: syn::ItemEnum) -> Result<TokenStream, Error> {
+    if is_integer_type(&attribs) {
+        return handle_integer_enum(attribs, enum_ty);
+    }
+
     let mut unit_variants = 0;
     let mut unnamed_variants = 0;
     let mut untagged_variants = false;
@@ -79,6 +83,12 @@ fn handle_string_enum(
         error!(fmt.span(), "illegal key 'format', will be autogenerated");
     }
 
+    let string_enum: bool = attribs
+        .remove("string_enum")
+        .map(TryFrom::try_from)
+        .transpose()?
+        .unwrap_or(false);
+
     let has_default_attrib = attribs.get("default").map(|def| def.span());
 
     let schema = {
@@ -105,6 +115,7 @@ fn handle_string_enum(
     let mut default_value = None;
 
     let mut variants = TokenStream::new();
+    let mut display_arms = TokenStream::new();
     let mut has_untagged_other = false;
     for variant in &mut enum_ty.variants {
         let checked_attrs = CheckedAttributes::from_slice(&variant.attrs);
@@ -116,6 +127,11 @@ fn handle_string_enum(
             syn::Fields::Unnamed(_) => {
                 if attrs.untagged.is_some() {
                     has_untagged_other = true;
+                    let variant_ident = &variant.ident;
+                    display_arms.extend(quote_spanned! { variant_ident.span() =>
+                        #checked_attrs
+                        Self::#variant_ident(value) => ::std::fmt::Display::fmt(value, f),
+                    });
                     continue;
                 } else {
                     bail!(variant => "unnamed variants not supported in string enums");
@@ -155,6 +171,12 @@ fn handle_string_enum(
             }
         }
 
+        let variant_ident = &variant.ident;
+        display_arms.extend(quote_spanned! { variant_ident.span() =>
+            #checked_attrs
+            Self::#variant_ident => f.write_str(#variant_string),
+        });
+
         variants.extend(quote_spanned! { variant.ident.span() =>
             #checked_attrs
             ::proxmox_schema::EnumEntry {
@@ -166,6 +188,31 @@ fn handle_string_enum(
 
     let name = &enum_ty.ident;
 
+    // `FromStr` goes through `Deserialize` to be consistent with all of serde's attributes:
+    let string_impls = if string_enum {
+        quote_spanned! { name.span() =>
+            impl ::std::fmt::Display for #name {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                    match self {
+                        #display_arms
+                    }
+                }
+            }
+
+            impl ::std::str::FromStr for #name {
+                type Err = ::serde::de::value::Error;
+
+                fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                    <Self as ::serde::Deserialize>::deserialize(
+                        ::serde::de::IntoDeserializer::into_deserializer(s),
+                    )
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
     let default_value = match default_value {
         Some(value) => quote_spanned!(value.span() => .default(#value)),
         None => TokenStream::new(),
@@ -192,6 +239,128 @@ fn handle_string_enum(
         impl ::proxmox_schema::UpdaterType for #name {
             type Updater = Option<Self>;
         }
+
+        #string_impls
+    })
+}
+
+fn is_integer_type(attribs: &JSONObject) -> bool {
+    match attribs.get("type") {
+        Some(JSONValue::Expr(syn::Expr::Path(path))) => path.path.is_ident("Integer"),
+        _ => false,
+    }
+}
+
+/// Unit enums with explicit discriminants can be represented by their numeric value by specifying
+/// `type: Integer`. The schema only allows the discriminant values, and the `Serialize` and
+/// `Deserialize` implementations are generated to use them as well.
+fn handle_integer_enum(
+    mut attribs: JSONObject,
+    enum_ty: syn::ItemEnum,
+) -> Result<TokenStream, Error> {
+    if let Some(fmt) = attribs.remove("format") {
+        error!(fmt.span(), "illegal key 'format', not supported on integer enums");
+    }
+
+    for derive in ["Serialize", "Deserialize"] {
+        if util::derives_trait(&enum_ty.attrs, derive) {
+            error!(
+                &enum_ty.ident =>
+                "integer enums must not derive {derive}, it is implemented by the api macro"
+            );
+        }
+    }
+
+    let schema = {
+        let mut schema: Schema = attribs.try_into()?;
+
+        if schema.description.is_none() {
+            let (comment, span) = util::get_doc_comments(&enum_ty.attrs)?;
+            if comment.is_empty() {
+                error!(
+                    Span::call_site(),
+                    "missing doc comment on enum for api-schema description"
+                );
+            }
+            schema.description = Maybe::Derived(syn::LitStr::new(comment.trim(), span));
+        }
+
+        let mut ts = TokenStream::new();
+        schema.to_typed_schema(&mut ts)?;
+        ts
+    };
+
+    let name = &enum_ty.ident;
+
+    let mut values = TokenStream::new();
+    let mut to_value = TokenStream::new();
+    let mut from_value = TokenStream::new();
+    for variant in &enum_ty.variants {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            bail!(variant.fields.span(), "integer enums cannot have fields");
+        }
+
+        if variant.discriminant.is_none() {
+            bail!(variant => "integer enum variants need an explicit discriminant");
+        }
+
+        let checked_attrs = CheckedAttributes::from_slice(&variant.attrs);
+        let variant_ident = &variant.ident;
+
+        values.extend(quote_spanned! { variant_ident.span() =>
+            #checked_attrs
+            #name::#variant_ident as i64,
+        });
+        to_value.extend(quote_spanned! { variant_ident.span() =>
+            #checked_attrs
+            #name::#variant_ident => #name::#variant_ident as i64,
+        });
+        from_value.extend(quote_spanned! { variant_ident.span() =>
+            #checked_attrs
+            if value == #name::#variant_ident as i64 {
+                return Ok(#name::#variant_ident);
+            }
+        });
+    }
+
+    Ok(quote_spanned! { name.span() =>
+        #enum_ty
+
+        impl ::proxmox_schema::ApiType for #name {
+            const API_SCHEMA: ::proxmox_schema::Schema =
+                #schema
+                .allowed_values(&[#values])
+                .schema();
+        }
+
+        impl ::proxmox_schema::UpdaterType for #name {
+            type Updater = Option<Self>;
+        }
+
+        impl ::serde::Serialize for #name {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+            {
+                serializer.serialize_i64(match self {
+                    #to_value
+                })
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for #name {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+            {
+                let value = <i64 as ::serde::Deserialize<'de>>::deserialize(deserializer)?;
+                #from_value
+                Err(::serde::de::Error::invalid_value(
+                    ::serde::de::Unexpected::Signed(value),
+                    &"a valid enum value",
+                ))
+            }
+        }
     })
 }
 
@@ -110,6 +110,10 @@ enum MethodFlavor {
     Normal,
     Serializing,
     Streaming,
+    /// Raw `ApiHandler::AsyncHttp` handler taking the request parts and body.
+    AsyncHttp,
+    /// Raw `ApiHandler::AsyncHttpBodyParameters` handler taking the request parts.
+    AsyncHttpBodyParameters,
 }
 
 struct MethodInfo {
@@ -127,8 +131,31 @@ struct MethodInfo {
 ///
 /// See the top level macro documentation for a complete example.
 pub fn handle_method(mut attribs: JSONObject, func: syn::ItemFn) -> Result<TokenStream, Error> {
+    let mut shared_input_schema = None;
     let input_schema: Schema = match attribs.remove("input") {
-        Some(input) => input.into_object("input schema definition")?.try_into()?,
+        Some(input) => {
+            let mut input = input.into_object("input schema definition")?;
+            shared_input_schema = input
+                .remove("schema")
+                .map(syn::Expr::try_from)
+                .transpose()?;
+            match &shared_input_schema {
+                Some(shared) => {
+                    for key in input.keys().filter(|key| key.as_str() != "properties") {
+                        error!(
+                            key.span(),
+                            "only 'properties' can be used together with a shared input schema",
+                        );
+                    }
+                    if input.contains_key("properties") {
+                        input.try_into()?
+                    } else {
+                        Schema::empty_object(shared.span())
+                    }
+                }
+                None => input.try_into()?,
+            }
+        }
         None => Schema {
             span: Span::call_site(),
             description: Maybe::None,
@@ -196,21 +223,35 @@ pub fn handle_method(mut attribs: JSONObject, func: syn::ItemFn) -> Result<Token
         .transpose()?
         .unwrap_or(syn::LitBool::new(false, Span::call_site()));
 
+    let mut flavor = match (serializing.value(), streaming.value()) {
+        (false, false) => MethodFlavor::Normal,
+        (true, false) => MethodFlavor::Serializing,
+        (false, true) => MethodFlavor::Streaming,
+        (true, true) => {
+            error!(&serializing => "'stream' and 'serializing' attributes are in conflict");
+            MethodFlavor::Normal
+        }
+    };
+
+    if let Some(http_flavor) = detect_http_handler(&func.sig) {
+        if let Some(asyncness) = &func.sig.asyncness {
+            error!(asyncness => "raw http handlers must not be async, return an ApiResponseFuture instead");
+        }
+        if serializing.value() {
+            error!(&serializing => "raw http handlers cannot be 'serializing'");
+        } else if streaming.value() {
+            error!(&streaming => "raw http handlers cannot use 'stream'");
+        }
+        flavor = http_flavor;
+    }
+
     let mut method_info = MethodInfo {
         input_schema,
         return_type,
         wrapper_ts: TokenStream::new(),
         default_consts: TokenStream::new(),
         is_async: func.sig.asyncness.is_some(),
-        flavor: match (serializing.value(), streaming.value()) {
-            (false, false) => MethodFlavor::Normal,
-            (true, false) => MethodFlavor::Serializing,
-            (false, true) => MethodFlavor::Streaming,
-            (true, true) => {
-                error!(serializing => "'stream' and 'serializing' attributes are in conflict");
-                MethodFlavor::Normal
-            }
-        },
+        flavor,
         func,
     };
 
@@ -247,6 +288,23 @@ pub fn handle_method(mut attribs: JSONObject, func: syn::ItemFn) -> Result<Token
         .transpose()?
         .unwrap_or(false);
 
+    let deprecated: bool = attribs
+        .remove("deprecated")
+        .map(TryFrom::try_from)
+        .transpose()?
+        .unwrap_or(false);
+
+    let deprecated_message_setter = match attribs.remove("deprecated_message") {
+        Some(message) => {
+            let message = syn::LitStr::try_from(message)?;
+            if !deprecated {
+                error!(&message => "'deprecated_message' requires 'deprecated: true'");
+            }
+            quote_spanned! { message.span() => .deprecated_message(Some(#message)) }
+        }
+        None => TokenStream::new(),
+    };
+
     if !attribs.is_empty() {
         error!(
             attribs.span(),
@@ -289,8 +347,21 @@ pub fn handle_method(mut attribs: JSONObject, func: syn::ItemFn) -> Result<Token
         func.sig.ident.span(),
     );
 
-    let (input_schema_code, input_schema_parameter) =
-        serialize_input_schema(input_schema, &func.sig.ident, func.sig.span())?;
+    let (input_schema_code, input_schema_parameter, parameter_list) = match shared_input_schema {
+        Some(shared) => (
+            check_shared_input_schema(&input_schema, &shared),
+            quote_spanned! { shared.span() =>
+                ::proxmox_schema::ParameterSchema::Object(&#shared)
+            },
+            TokenStream::new(),
+        ),
+        None => {
+            let parameter_list = serialize_parameter_list(&input_schema, vis, func_name);
+            let (code, parameter) =
+                serialize_input_schema(input_schema, &func.sig.ident, func.sig.span())?;
+            (code, parameter, parameter_list)
+        }
+    };
 
     let mut returns_schema_setter = TokenStream::new();
     if let Some(return_type) = return_type {
@@ -318,6 +389,12 @@ pub fn handle_method(mut attribs: JSONObject, func: syn::ItemFn) -> Result<Token
         (MethodFlavor::Streaming, false) => {
             quote! { ::proxmox_router::ApiHandler::StreamSync(&#api_func_name) }
         }
+        (MethodFlavor::AsyncHttp, _) => {
+            quote! { ::proxmox_router::ApiHandler::AsyncHttp(&#api_func_name) }
+        }
+        (MethodFlavor::AsyncHttpBodyParameters, _) => {
+            quote! { ::proxmox_router::ApiHandler::AsyncHttpBodyParameters(&#api_func_name) }
+        }
     };
 
     Ok(quote_spanned! { func.sig.span() =>
@@ -332,7 +409,11 @@ pub fn handle_method(mut attribs: JSONObject, func: syn::ItemFn) -> Result<Token
             #access_setter
             .reload_timezone(#reload_timezone)
             .protected(#protected)
-            .unstable(#unstable);
+            .unstable(#unstable)
+            .deprecated(#deprecated)
+            #deprecated_message_setter;
+
+        #parameter_list
 
         #default_consts
 
@@ -373,7 +454,47 @@ fn check_input_type(input: &syn::FnArg) -> Result<(&syn::PatType, &syn::PatIdent
     Ok((pat_type, pat))
 }
 
+/// Check whether the function is a raw http handler, which is the case if its first parameter is
+/// the request's `Parts`.
+///
+/// With 5 parameters this is an `AsyncHttp` handler:
+/// `(Parts, Incoming, Value, &'static ApiMethod, Box<dyn RpcEnvironment>)`, with 4 parameters it is
+/// an `AsyncHttpBodyParameters` handler without the body.
+fn detect_http_handler(sig: &syn::Signature) -> Option<MethodFlavor> {
+    let first = match sig.inputs.first()? {
+        syn::FnArg::Typed(pat_type) => &pat_type.ty,
+        syn::FnArg::Receiver(_) => return None,
+    };
+
+    let is_parts = match &**first {
+        syn::Type::Path(p) => {
+            p.qself.is_none() && p.path.segments.last().is_some_and(|ps| ps.ident == "Parts")
+        }
+        _ => false,
+    };
+    if !is_parts {
+        return None;
+    }
+
+    match sig.inputs.len() {
+        5 => Some(MethodFlavor::AsyncHttp),
+        4 => Some(MethodFlavor::AsyncHttpBodyParameters),
+        _ => {
+            error!(
+                &sig.inputs =>
+                "raw http handlers take either 4 or 5 parameters, starting with the request's Parts",
+            );
+            None
+        }
+    }
+}
+
 fn handle_function_signature(method_info: &mut MethodInfo) -> Result<Ident, Error> {
+    if let MethodFlavor::AsyncHttp | MethodFlavor::AsyncHttpBodyParameters = method_info.flavor {
+        // raw http handlers are used as they are, their parameters are not extracted
+        return Ok(method_info.func.sig.ident.clone());
+    }
+
     let sig = &method_info.func.sig;
 
     let mut api_method_param = None;
@@ -404,6 +525,11 @@ fn handle_function_signature(method_info: &mut MethodInfo) -> Result<Ident, Erro
             }
             if has_default && !entry.optional.expect_bool() {
                 error!(pat_type => "non-optional parameter cannot have a default");
+            } else if is_option && !entry.optional.expect_bool() {
+                error!(
+                    pat_type =>
+                    "non-optional parameter cannot be an Option<T>, use T or mark it as optional",
+                );
             }
         } else {
             continue;
@@ -447,6 +573,19 @@ fn handle_function_signature(method_info: &mut MethodInfo) -> Result<Ident, Erro
             .input_schema
             .find_obj_property_by_ident(&pat_ident.to_string())
         {
+            // parameters are removed from the input map, so they cannot be borrowed from it:
+            if let Some(reference) = find_reference_type(&pat_type.ty) {
+                let elem = &reference.elem;
+                if is_str_type(elem) {
+                    bail!(reference => "use String instead of &str for api parameters");
+                }
+                bail!(
+                    reference =>
+                    "api parameters are extracted as owned values, use {} instead of &{}",
+                    elem.to_token_stream(),
+                    elem.to_token_stream(),
+                );
+            }
             if let SchemaItem::Inferred(span) = &entry.schema.item {
                 bail!(*span, "failed to infer type");
             }
@@ -513,6 +652,21 @@ fn is_rpc_env_type(ty: &syn::Type) -> bool {
     false
 }
 
+/// Find a reference in a parameter type, looking through an `Option<>`.
+fn find_reference_type(ty: &syn::Type) -> Option<&syn::TypeReference> {
+    match util::is_option_type(ty).unwrap_or(ty) {
+        syn::Type::Reference(r) => Some(r),
+        _ => None,
+    }
+}
+
+fn is_str_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(p) => p.qself.is_none() && p.path.is_ident("str"),
+        _ => false,
+    }
+}
+
 /// Note that we cannot handle renamed imports at all here...
 fn is_value_type(ty: &syn::Type) -> bool {
     if let syn::Type::Path(p) = ty {
@@ -620,6 +774,9 @@ fn create_wrapper_function(
                 }
             }
         }
+        MethodFlavor::AsyncHttp | MethodFlavor::AsyncHttpBodyParameters => {
+            unreachable!("raw http handlers do not get a wrapper function")
+        }
     };
 
     match (method_info.flavor, method_info.is_async) {
@@ -699,6 +856,9 @@ fn create_wrapper_function(
                 }
             });
         }
+        (MethodFlavor::AsyncHttp | MethodFlavor::AsyncHttpBodyParameters, _) => {
+            unreachable!("raw http handlers do not get a wrapper function")
+        }
     }
 
     Ok(api_func_name)
@@ -770,11 +930,8 @@ fn extract_normal_parameter(
                     });
                 }
             } else if param.entry.optional.expect_bool() && no_option_type {
-                // FIXME: we should not be able to reach this without having produced another
-                // error above already anyway?
-                error!(param.ty => "Optional parameter without Option<T> requires a default");
-
-                // we produced an error so just write something that will compile
+                // `handle_function_signature` already produced an error for this case, so just
+                // write something that will compile
                 body.extend(quote_spanned! { span =>
                     .unwrap_or_else(|| unreachable!())
                 });
@@ -824,6 +981,109 @@ fn extract_normal_parameter(
     Ok(())
 }
 
+/// Produce the `API_PARAMETERS_<NAME>` list of `(name, optional)` pairs for tooling.
+///
+/// Flattened parameters are not included, since their properties are not known to the macro.
+fn serialize_parameter_list(
+    input_schema: &Schema,
+    vis: &syn::Visibility,
+    func_name: &Ident,
+) -> TokenStream {
+    let list_name = Ident::new(
+        &format!("API_PARAMETERS_{}", func_name.to_string().to_uppercase()),
+        func_name.span(),
+    );
+
+    let mut entries = TokenStream::new();
+    if let Some(obj) = input_schema.as_object() {
+        for entry in obj
+            .properties_
+            .iter()
+            .filter(|entry| entry.flatten.is_none())
+        {
+            let name = entry.name.as_str();
+            let optional = &entry.optional;
+            let attrs = &entry.attrs;
+            entries.extend(quote! { #attrs (#name, #optional), });
+        }
+    }
+
+    // use the method's visibility like `API_METHOD_<NAME>`, a private list may well be unused
+    let allow_dead_code = match vis {
+        syn::Visibility::Public(_) => TokenStream::new(),
+        _ => quote! { #[allow(dead_code)] },
+    };
+
+    quote_spanned! { func_name.span() =>
+        #allow_dead_code
+        #vis const #list_name: &[(&str, bool)] = &[#entries];
+    }
+}
+
+/// Produce a compile time check that the properties declared next to a shared input schema are
+/// part of that schema and agree on whether they are optional.
+fn check_shared_input_schema(input_schema: &Schema, shared: &syn::Expr) -> TokenStream {
+    let mut checks = TokenStream::new();
+    if let Some(obj) = input_schema.as_object() {
+        for entry in obj
+            .properties_
+            .iter()
+            .filter(|entry| entry.flatten.is_none())
+        {
+            let name = entry.name.as_str();
+            let optional = entry.optional.expect_bool();
+            let attrs = &entry.attrs;
+            let missing = format!("parameter '{name}' is not part of the shared input schema");
+            let mismatch = if optional {
+                format!("parameter '{name}' is required in the shared input schema")
+            } else {
+                format!("parameter '{name}' is optional in the shared input schema")
+            };
+            checks.extend(quote_spanned! { entry.name.span() =>
+                #attrs
+                const _: () = match lookup(#shared.properties, #name) {
+                    Some(#optional) => (),
+                    Some(_) => panic!(#mismatch),
+                    None => panic!(#missing),
+                };
+            });
+        }
+    }
+
+    if checks.is_empty() {
+        return checks;
+    }
+
+    quote_spanned! { shared.span() =>
+        const _: () = {
+            const fn lookup(
+                properties: ::proxmox_schema::SchemaPropertyMap,
+                name: &str,
+            ) -> Option<bool> {
+                let name = name.as_bytes();
+                let mut i = 0;
+                while i != properties.len() {
+                    let (property, optional, _) = properties[i];
+                    let property = property.as_bytes();
+                    if property.len() == name.len() {
+                        let mut j = 0;
+                        while j != name.len() && property[j] == name[j] {
+                            j += 1;
+                        }
+                        if j == name.len() {
+                            return Some(optional);
+                        }
+                    }
+                    i += 1;
+                }
+                None
+            }
+
+            #checks
+        };
+    }
+}
+
 /// Returns a tuple containing the schema code first and the `ParameterSchema` parameter for the
 /// `ApiMethod` second.
 fn serialize_input_schema(
@@ -113,18 +113,49 @@ impl TryFrom<JSONObject> for Schema {
                 .transpose()?,
         );
 
+        let span = obj.span();
+        let item = SchemaItem::try_extract_from(&mut obj)?;
+        let properties: Vec<(Ident, syn::Expr)> = obj
+            .into_iter()
+            .map(|(key, value)| Ok((key.into_ident(), value.try_into()?)))
+            .collect::<Result<_, syn::Error>>()?;
+
+        check_length_limits(&properties);
+
         Ok(Self {
-            span: obj.span(),
+            span,
             description,
-            item: SchemaItem::try_extract_from(&mut obj)?,
-            properties: obj
-                .into_iter()
-                .map(|(key, value)| Ok((key.into_ident(), value.try_into()?)))
-                .collect::<Result<_, syn::Error>>()?,
+            item,
+            properties,
         })
     }
 }
 
+/// If both `min_length` and `max_length` are integer literals, make sure the schema can actually
+/// match something.
+fn check_length_limits(properties: &[(Ident, syn::Expr)]) {
+    let find_limit = |name: &str| {
+        properties.iter().find_map(|(key, value)| match value {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(int),
+                ..
+            }) if key == name => Some((int, int.base10_parse::<u64>().ok()?)),
+            _ => None,
+        })
+    };
+
+    if let (Some((_, min)), Some((max_lit, max))) =
+        (find_limit("min_length"), find_limit("max_length"))
+        && min > max
+    {
+        error!(
+            max_lit => "min_length ({}) is greater than max_length ({})",
+            min,
+            max,
+        );
+    }
+}
+
 impl Schema {
     fn blank(span: Span) -> Self {
         Self {
@@ -290,7 +321,7 @@ impl SchemaItem {
             .ok_or_else(|| format_err!(&ty.path => "invalid empty path"))?
             .ident;
 
-        if name == "Null" {
+        if name == "Null" || name == "null" {
             Ok(SchemaItem::Null(ty.span()))
         } else if name == "Boolean" || name == "bool" {
             Ok(SchemaItem::Boolean(ty.span()))
@@ -321,10 +352,15 @@ impl SchemaItem {
 
         match self {
             SchemaItem::Null(span) => {
-                let description = check_description()?;
-                ts.extend(quote_spanned! { *span =>
-                    ::proxmox_schema::NullSchema::new(#description)
-                });
+                if !properties.is_empty() {
+                    error!(&properties[0].0 => "additional properties not allowed on null schema");
+                }
+                if let Maybe::Explicit(description) = description {
+                    error!(description => "description not allowed on null schema");
+                }
+
+                ts.extend(quote_spanned! { *span => ::proxmox_schema::Schema::Null });
+                return Ok(true);
             }
             SchemaItem::Boolean(span) => {
                 let description = check_description()?;
@@ -663,6 +699,12 @@ impl SchemaObject {
                             .transpose()?
                             .and_then(|(span, value)| if value { Some(span) } else { None });
 
+                        // `rename` changes the property name while keeping the identifier:
+                        let key = match schema.remove("rename") {
+                            Some(rename) => key.renamed(syn::LitStr::try_from(rename)?.value()),
+                            None => key,
+                        };
+
                         properties.push(
                             ObjectEntry::new(key, optional, schema.try_into()?)
                                 .with_flatten(flatten),
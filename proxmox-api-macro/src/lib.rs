@@ -144,6 +144,10 @@ fn router_do(item: TokenStream) -> Result<TokenStream, Error> {
             )
         )
         .protected(false);
+    const API_PARAMETERS_CREATE_TICKET: &[(&str, bool)] = &[
+        ("password", false),
+        ("username", false),
+    ];
     fn create_ticket(
         param: Value,
         info: &ApiMethod,
@@ -153,6 +157,327 @@ fn router_do(item: TokenStream) -> Result<TokenStream, Error> {
     }
     ```
 
+    Every key in the schema may only appear once. Listing a property twice, for instance by
+    accident when copy-pasting, produces an error pointing at the second occurrence:
+
+    ```compile_fail
+    # use proxmox_api_macro::api;
+    # use anyhow::Error;
+    #[api(
+        input: {
+            properties: {
+                name: { type: String, description: "A name." },
+                name: { type: String, description: "The same name again." },
+            },
+        },
+    )]
+    /// Duplicated property.
+    fn duplicate_property(name: String) -> Result<(), Error> {
+        let _ = name;
+        Ok(())
+    }
+    ```
+
+    Parameters are moved out of the input object, so they must be owned types. Use `String`
+    instead of `&str`:
+
+    ```compile_fail
+    # use proxmox_api_macro::api;
+    # use anyhow::Error;
+    #[api(
+        input: {
+            properties: {
+                name: { type: String, description: "A name." },
+            },
+        },
+    )]
+    /// Borrowed parameter.
+    fn borrowed_parameter(name: &str) -> Result<(), Error> {
+        let _ = name;
+        Ok(())
+    }
+    ```
+
+    The Rust type of a parameter has to match its `optional` flag and `default` value:
+
+    * Required parameters use a plain `T`.
+    * Optional parameters without a `default` use `Option<T>`.
+    * Optional parameters with a `default` use either `T`, in which case the default is filled in
+      when the parameter is missing, or `Option<T>`.
+
+    Contradicting combinations are rejected, for instance an `Option<T>` for a required parameter:
+
+    ```compile_fail
+    # use proxmox_api_macro::api;
+    # use anyhow::Error;
+    #[api(
+        input: {
+            properties: {
+                name: { type: String, description: "A name.", optional: false },
+            },
+        },
+    )]
+    /// Required parameter with an optional type.
+    fn required_option(name: Option<String>) -> Result<(), Error> {
+        let _ = name;
+        Ok(())
+    }
+    ```
+
+    An optional parameter without a default value which is not an `Option<T>`:
+
+    ```compile_fail
+    # use proxmox_api_macro::api;
+    # use anyhow::Error;
+    #[api(
+        input: {
+            properties: {
+                count: { type: Integer, description: "A count.", optional: true },
+            },
+        },
+    )]
+    /// Optional parameter without a default.
+    fn optional_without_default(count: i64) -> Result<(), Error> {
+        let _ = count;
+        Ok(())
+    }
+    ```
+
+    Or a required parameter with a default value:
+
+    ```compile_fail
+    # use proxmox_api_macro::api;
+    # use anyhow::Error;
+    #[api(
+        input: {
+            properties: {
+                count: { type: Integer, description: "A count.", default: 1 },
+            },
+        },
+    )]
+    /// Required parameter with a default.
+    fn required_with_default(count: i64) -> Result<(), Error> {
+        let _ = count;
+        Ok(())
+    }
+    ```
+
+    Length limits which can never be satisfied are rejected as well:
+
+    ```compile_fail
+    # use proxmox_api_macro::api;
+    # use anyhow::Error;
+    #[api(
+        input: {
+            properties: {
+                name: { type: String, description: "A name.", min_length: 10, max_length: 4 },
+            },
+        },
+    )]
+    /// Impossible length limits.
+    fn impossible_length(name: String) -> Result<(), Error> {
+        let _ = name;
+        Ok(())
+    }
+    ```
+
+    If the method returns a type which already implements `ApiType` (for instance a type declared
+    with `#[api]`, see below), the `returns` schema can simply refer to it instead of repeating its
+    description. This uses the type's `API_SCHEMA`:
+
+    ```
+    # use proxmox_api_macro::api;
+    # use anyhow::Error;
+    # use serde::Serialize;
+    #[api]
+    #[derive(Serialize)]
+    /// A color.
+    pub enum Color {
+        /// Red.
+        Red,
+        /// Blue.
+        Blue,
+    }
+
+    #[api(returns: { type: Color })]
+    /// Get the current color.
+    fn get_color() -> Result<Color, Error> {
+        Ok(Color::Red)
+    }
+    ```
+
+    Methods returning nothing can document this explicitly with `returns: { type: null }`. The
+    resulting `ApiMethod` uses the null schema, just like one without a `returns` block, but has
+    its `returns_declared` flag set:
+
+    ```
+    # use proxmox_api_macro::api;
+    # use anyhow::Error;
+    #[api(returns: { type: null })]
+    /// Clear the cache.
+    fn clear_cache() -> Result<(), Error> {
+        Ok(())
+    }
+    ```
+
+    Required permissions can be declared with an `access` block. The `permission` is a reference
+    to a [`Permission`](proxmox_router::Permission) and the `description` is optional. Without an
+    `access` block the method keeps the default of `ApiMethod`, which is
+    [`Permission::Superuser`](proxmox_router::Permission::Superuser) without a description.
+
+    ```
+    # use proxmox_api_macro::api;
+    # use anyhow::Error;
+    use proxmox_router::Permission;
+
+    #[api(
+        access: {
+            description: "Anyone can get the version.",
+            permission: &Permission::Anybody,
+        },
+    )]
+    /// Get the version.
+    fn get_version() -> Result<String, Error> {
+        Ok("1.0".to_string())
+    }
+    ```
+
+    Endpoints sharing a large parameter set can refer to a common `ObjectSchema` constant via
+    `schema` in their `input` block. The referenced schema is used for the `ApiMethod` as is,
+    including its description. Since the macro cannot look into the constant, parameters which
+    should be extracted into function arguments still need to be declared in `properties`;
+    everything else has to be taken from a catch-all `Value` parameter:
+
+    ```
+    # use proxmox_api_macro::api;
+    # use anyhow::Error;
+    # use serde_json::Value;
+    use proxmox_schema::{BooleanSchema, ObjectSchema, StringSchema};
+
+    const SHARED_PARAMETERS: ObjectSchema = ObjectSchema::new(
+        "Common parameters.",
+        &[
+            ("name", false, &StringSchema::new("A name.").schema()),
+            ("verbose", true, &BooleanSchema::new("Verbose output.").schema()),
+        ],
+    );
+
+    #[api(
+        input: {
+            schema: SHARED_PARAMETERS,
+            properties: {
+                name: { type: String, description: "A name." },
+            },
+        },
+    )]
+    /// Uses the shared parameters.
+    fn shared(name: String, param: Value) -> Result<(), Error> {
+        let _ = (name, param);
+        Ok(())
+    }
+    ```
+
+    The declared properties are checked against the shared schema at compile time, so they must
+    exist there with the same `optional` flag. Declaring `verbose` as required in the example above
+    fails to compile:
+
+    ```compile_fail
+    # use proxmox_api_macro::api;
+    # use anyhow::Error;
+    # use serde_json::Value;
+    # use proxmox_schema::{BooleanSchema, ObjectSchema, StringSchema};
+    # const SHARED_PARAMETERS: ObjectSchema = ObjectSchema::new(
+    #     "Common parameters.",
+    #     &[
+    #         ("name", false, &StringSchema::new("A name.").schema()),
+    #         ("verbose", true, &BooleanSchema::new("Verbose output.").schema()),
+    #     ],
+    # );
+    #[api(
+        input: {
+            schema: SHARED_PARAMETERS,
+            properties: {
+                verbose: { type: bool, description: "Verbose output." },
+            },
+        },
+    )]
+    /// Disagrees with the shared parameters.
+    fn mismatch(verbose: bool, param: Value) -> Result<(), Error> {
+        let _ = (verbose, param);
+        Ok(())
+    }
+    ```
+
+    Since the complete parameter list is only known to the shared schema, no
+    `API_PARAMETERS_<NAME>` list is generated for such methods, use the schema's `properties`
+    instead.
+
+    Methods which should no longer be used can be marked as `deprecated`, optionally with a
+    `deprecated_message`, for instance to point to a replacement. This sets the
+    `deprecated` and `deprecated_message` fields of the generated `ApiMethod`:
+
+    ```
+    # use proxmox_api_macro::api;
+    # use anyhow::Error;
+    #[api(
+        deprecated: true,
+        deprecated_message: "Use 'get_version' instead.",
+    )]
+    /// Get the version (old API).
+    fn version() -> Result<String, Error> {
+        Ok("1.0".to_string())
+    }
+    ```
+
+    If a property's name on the wire is not a suitable Rust identifier, the property can be
+    declared under the parameter's name and carry a `rename` with the actual property name:
+
+    ```
+    # use proxmox_api_macro::api;
+    # use anyhow::Error;
+    #[api(
+        input: {
+            properties: {
+                csrf_token: {
+                    rename: "CSRFPreventionToken",
+                    type: String,
+                    description: "Cross Site Request Forgery Prevention Token.",
+                },
+            },
+        },
+    )]
+    /// Check a token.
+    fn check_token(csrf_token: String) -> Result<(), Error> {
+        let _ = csrf_token;
+        Ok(())
+    }
+    ```
+
+    Functions which need raw access to the request, for instance for uploads or downloads, take
+    the request's `Parts` as their first parameter. They are used as handler directly, without
+    extracting any parameters. With the request body as second parameter this creates an
+    `ApiHandler::AsyncHttp` method, without it an `ApiHandler::AsyncHttpBodyParameters` method:
+
+    ```ignore
+    #[api(
+        input: {
+            properties: {
+                name: { type: String, description: "The file name." },
+            },
+        },
+    )]
+    /// Upload a file.
+    fn upload_file(
+        parts: Parts,
+        req_body: Incoming,
+        param: Value,
+        info: &'static ApiMethod,
+        rpcenv: Box<dyn RpcEnvironment>,
+    ) -> ApiResponseFuture {
+        Box::pin(async move { ... })
+    }
+    ```
+
     The `#[api]` macro can also be used on type declarations to create schemas for `struct` and
     `enum` types to be used instead of accessing json values via string indexing.
 
@@ -242,11 +567,13 @@ fn router_do(item: TokenStream) -> Result<TokenStream, Error> {
 
     # `enum` types:
 
-    The `#[api]` macro supports 2 distinct versions of `enum` types:
+    The `#[api]` macro supports 3 distinct versions of `enum` types:
 
     - Simple enums, which become `String` types using the "format" `ApiStringFormat::Enum`. A
       single `#[serde(untagged)]` variant is allowed to support "unknown" strings.
     - Enums with only newtype variants.
+    - Simple enums with explicit discriminants and `type: Integer`, which become `Integer` types
+      restricted to the discriminant values.
 
     ## Simple enums.
 
@@ -291,6 +618,61 @@ fn router_do(item: TokenStream) -> Result<TokenStream, Error> {
     }
     ```
 
+    Setting `string_enum: true` additionally implements `Display` and `FromStr` for the enum. The
+    strings match the serde representation, so the enum also needs to implement `Deserialize`.
+
+    ```
+    # use proxmox_api_macro::api;
+    # use serde::{Deserialize, Serialize};
+    #[api(string_enum: true)]
+    #[derive(Deserialize, Serialize)]
+    #[serde(rename_all = "kebab-case")]
+    /// Some Description.
+    pub enum StringEnum {
+        /// Mandatory description 1.
+        VariantOne,
+        /// Mandatory description 2.
+        VariantTwo,
+    }
+
+    assert_eq!(StringEnum::VariantOne.to_string(), "variant-one");
+    assert!(matches!("variant-two".parse(), Ok(StringEnum::VariantTwo)));
+    ```
+
+    ## Integer enums.
+
+    When specifying `type: Integer`, all variants need an explicit discriminant. The schema only
+    allows these values, and since the enum is represented by its discriminant values, the
+    `Serialize` and `Deserialize` implementations are generated by the macro and must not be
+    derived.
+
+    ```no_run
+    # use proxmox_api_macro::api;
+    #[api(type: Integer)]
+    /// Some Description.
+    pub enum Priority {
+        /// Low priority.
+        Low = 1,
+        /// High priority.
+        High = 10,
+    }
+    ```
+
+    This produces the following schema:
+
+    ```no_run
+    # use proxmox_schema::ApiType;
+    # use proxmox_schema::IntegerSchema;
+    # use proxmox_schema::Schema;
+    # pub enum Priority { Low = 1, High = 10 }
+    impl ApiType for Priority {
+        const API_SCHEMA: Schema =
+            IntegerSchema::new("Some Description.")
+                .allowed_values(&[Priority::Low as i64, Priority::High as i64])
+                .schema();
+    }
+    ```
+
     ## `OneOf` schema `enum`s.
 
     For `enum`s with newtype variants, the `#[api]` macro currently supports internally and
@@ -43,6 +43,11 @@ impl FieldName {
         }
     }
 
+    /// Change the string version of the name while keeping the identifier.
+    pub fn renamed(self, string: String) -> Self {
+        Self { string, ..self }
+    }
+
     #[inline]
     pub fn as_str(&self) -> &str {
         &self.string
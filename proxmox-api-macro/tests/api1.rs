@@ -235,6 +235,31 @@ pub fn basic_function() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn access_check() {
+    assert_eq!(
+        API_METHOD_CREATE_TICKET.access.description,
+        Some("Only root can access this."),
+    );
+    assert_eq!(
+        API_METHOD_CREATE_TICKET.access.permission,
+        &Permission::Superuser
+    );
+
+    assert_eq!(API_METHOD_CREATE_TICKET_DIRECT.access.description, None);
+    assert_eq!(
+        API_METHOD_CREATE_TICKET_DIRECT.access.permission,
+        &Permission::World
+    );
+
+    // without an access block the default of `ApiMethod` is kept
+    assert_eq!(API_METHOD_BASIC_FUNCTION.access.description, None);
+    assert_eq!(
+        API_METHOD_BASIC_FUNCTION.access.permission,
+        &Permission::Superuser
+    );
+}
+
 #[api(
     serializing: true,
 )]
@@ -132,3 +132,56 @@ pub fn get_some_text() -> Result<String, Error> {
 pub fn get_some_text_data() -> Result<Value, Error> {
     Ok(json!({ "text": get_some_text()? }))
 }
+
+pub const SHARED_OBJECT_SCHEMA: schema::ObjectSchema = schema::ObjectSchema::new(
+    "Shared archive parameters.",
+    &[
+        ("archive-name", false, &NAME_SCHEMA),
+        (
+            "verbose",
+            true,
+            &schema::BooleanSchema::new("Verbose output.").schema(),
+        ),
+    ],
+);
+
+#[api(
+    input: {
+        schema: SHARED_OBJECT_SCHEMA,
+        properties: {
+            "archive-name": {
+                schema: NAME_SCHEMA,
+            },
+        },
+    },
+)]
+/// Restore an archive.
+pub fn restore_archive(archive_name: String, param: Value) -> Result<(), Error> {
+    let _ = archive_name;
+    let _ = param;
+    Ok(())
+}
+
+#[api(input: { schema: SHARED_OBJECT_SCHEMA })]
+/// Verify an archive.
+pub fn verify_archive(param: Value) -> Result<(), Error> {
+    let _ = param;
+    Ok(())
+}
+
+#[test]
+fn shared_input_schema_check() {
+    const RESTORE_METHOD: ::proxmox_router::ApiMethod = ::proxmox_router::ApiMethod::new(
+        &::proxmox_router::ApiHandler::Sync(&api_function_restore_archive),
+        &SHARED_OBJECT_SCHEMA,
+    )
+    .protected(false);
+    assert_eq!(RESTORE_METHOD, API_METHOD_RESTORE_ARCHIVE);
+
+    const VERIFY_METHOD: ::proxmox_router::ApiMethod = ::proxmox_router::ApiMethod::new(
+        &::proxmox_router::ApiHandler::Sync(&api_function_verify_archive),
+        &SHARED_OBJECT_SCHEMA,
+    )
+    .protected(false);
+    assert_eq!(VERIFY_METHOD, API_METHOD_VERIFY_ARCHIVE);
+}
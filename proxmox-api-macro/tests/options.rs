@@ -87,3 +87,106 @@ fn test_invocations() {
             .expect("func with option should work");
     assert_eq!(value, 5);
 }
+
+#[api(
+    input: {
+        properties: {
+            csrf_token: {
+                rename: "CSRFPreventionToken",
+                type: String,
+                description: "Cross Site Request Forgery Prevention Token.",
+            },
+            node: {
+                type: String,
+                description: "The node name.",
+                optional: true,
+            },
+        },
+    },
+)]
+/// Returns the token.
+pub fn renamed_parameter(csrf_token: String, node: Option<String>) -> Result<String, Error> {
+    let _ = node;
+    Ok(csrf_token)
+}
+
+#[test]
+fn renamed_parameter_check() {
+    const TEST_METHOD: ::proxmox_router::ApiMethod = ::proxmox_router::ApiMethod::new(
+        &::proxmox_router::ApiHandler::Sync(&api_function_renamed_parameter),
+        &::proxmox_schema::ObjectSchema::new(
+            "Returns the token.",
+            &[
+                (
+                    "CSRFPreventionToken",
+                    false,
+                    &::proxmox_schema::StringSchema::new(
+                        "Cross Site Request Forgery Prevention Token.",
+                    )
+                    .schema(),
+                ),
+                (
+                    "node",
+                    true,
+                    &::proxmox_schema::StringSchema::new("The node name.").schema(),
+                ),
+            ],
+        ),
+    )
+    .protected(false);
+
+    assert_eq!(TEST_METHOD, API_METHOD_RENAMED_PARAMETER);
+
+    let mut env = RpcEnv;
+    let value = api_function_renamed_parameter(
+        json!({"CSRFPreventionToken": "token"}),
+        &API_METHOD_RENAMED_PARAMETER,
+        &mut env,
+    )
+    .expect("renamed parameter should be extracted");
+    assert_eq!(value, "token");
+
+    api_function_renamed_parameter(
+        json!({"csrf_token": "token"}),
+        &API_METHOD_RENAMED_PARAMETER,
+        &mut env,
+    )
+    .expect_err("the rust name must not be accepted as property name");
+}
+
+#[test]
+fn parameter_list_check() {
+    assert_eq!(
+        API_PARAMETERS_RENAMED_PARAMETER,
+        &[("CSRFPreventionToken", false), ("node", true)],
+    );
+    assert_eq!(API_PARAMETERS_TEST_OPTION, &[("value", true)]);
+}
+
+#[api(deprecated: true, deprecated_message: "Use 'renamed_parameter' instead.")]
+/// Old way to get the token.
+pub fn deprecated_method() -> Result<(), Error> {
+    Ok(())
+}
+
+#[api(deprecated: true)]
+/// Deprecated without a message.
+pub fn deprecated_without_message() -> Result<(), Error> {
+    Ok(())
+}
+
+#[test]
+fn deprecated_check() {
+    let deprecation =
+        |method: &::proxmox_router::ApiMethod| (method.deprecated, method.deprecated_message);
+
+    assert_eq!(
+        deprecation(&API_METHOD_DEPRECATED_METHOD),
+        (true, Some("Use 'renamed_parameter' instead.")),
+    );
+    assert_eq!(
+        deprecation(&API_METHOD_DEPRECATED_WITHOUT_MESSAGE),
+        (true, None),
+    );
+    assert_eq!(deprecation(&API_METHOD_RENAMED_PARAMETER), (false, None));
+}
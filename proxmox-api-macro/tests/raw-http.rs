@@ -0,0 +1,69 @@
+//! Test raw http handlers, which get the request parts instead of extracted parameters.
+
+use anyhow::format_err;
+use hyper::body::Incoming;
+use hyper::http::request::Parts;
+use serde_json::Value;
+
+use proxmox_api_macro::api;
+use proxmox_router::{ApiMethod, ApiResponseFuture, RpcEnvironment};
+
+#[api(
+    input: {
+        properties: {
+            name: {
+                type: String,
+                description: "The file name.",
+            },
+        },
+    },
+)]
+/// Upload a file.
+pub fn upload_file(
+    parts: Parts,
+    req_body: Incoming,
+    param: Value,
+    info: &'static ApiMethod,
+    rpcenv: Box<dyn RpcEnvironment>,
+) -> ApiResponseFuture {
+    let _ = (parts, req_body, param, info, rpcenv);
+    Box::pin(async move { Err(format_err!("not implemented")) })
+}
+
+#[api]
+/// Download a file.
+pub fn download_file(
+    parts: Parts,
+    param: Value,
+    info: &'static ApiMethod,
+    rpcenv: Box<dyn RpcEnvironment>,
+) -> ApiResponseFuture {
+    let _ = (parts, param, info, rpcenv);
+    Box::pin(async move { Err(format_err!("not implemented")) })
+}
+
+#[test]
+fn raw_http_handlers() {
+    const UPLOAD_METHOD: ::proxmox_router::ApiMethod = ::proxmox_router::ApiMethod::new(
+        &::proxmox_router::ApiHandler::AsyncHttp(&upload_file),
+        &::proxmox_schema::ObjectSchema::new(
+            "Upload a file.",
+            &[(
+                "name",
+                false,
+                &::proxmox_schema::StringSchema::new("The file name.").schema(),
+            )],
+        ),
+    )
+    .protected(false);
+
+    assert_eq!(UPLOAD_METHOD, API_METHOD_UPLOAD_FILE);
+
+    const DOWNLOAD_METHOD: ::proxmox_router::ApiMethod = ::proxmox_router::ApiMethod::new(
+        &::proxmox_router::ApiHandler::AsyncHttpBodyParameters(&download_file),
+        &::proxmox_schema::ObjectSchema::new("Download a file.", &[]),
+    )
+    .protected(false);
+
+    assert_eq!(DOWNLOAD_METHOD, API_METHOD_DOWNLOAD_FILE);
+}
@@ -10,7 +10,7 @@ use proxmox_schema as schema;
 use proxmox_schema::{ApiType, EnumEntry};
 
 use anyhow::Error;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 pub const TEXT_SCHEMA: schema::Schema = schema::StringSchema::new("Text.").schema();
@@ -129,7 +129,7 @@ fn renamed_struct() {
 }
 
 #[api]
-#[derive(Default, Deserialize)]
+#[derive(Default, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 /// A selection of either 'onekind', 'another-kind' or 'selection-number-three'.
 pub enum Selection {
@@ -162,6 +162,40 @@ fn selection_test() {
     assert_eq!(TEST_SCHEMA, Selection::API_SCHEMA);
 }
 
+#[api]
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+/// Lowercase variants with an explicit rename.
+pub enum LowercaseWithRename {
+    /// Renamed by the container attribute.
+    FirstKind,
+    /// Explicitly renamed.
+    #[serde(rename = "Second-Kind", alias = "second")]
+    SecondKind,
+    /// Also renamed by the container attribute.
+    ThirdKind,
+}
+
+#[test]
+fn rename_precedence_test() {
+    const TEST_SCHEMA: ::proxmox_schema::Schema =
+        ::proxmox_schema::StringSchema::new("Lowercase variants with an explicit rename.")
+            .format(&::proxmox_schema::ApiStringFormat::Enum(&[
+                EnumEntry::new("firstkind", "Renamed by the container attribute."),
+                EnumEntry::new("Second-Kind", "Explicitly renamed."),
+                EnumEntry::new("thirdkind", "Also renamed by the container attribute."),
+            ]))
+            .schema();
+
+    assert_eq!(TEST_SCHEMA, LowercaseWithRename::API_SCHEMA);
+
+    // the schema values must match what serde actually accepts
+    let value: LowercaseWithRename = serde_json::from_str("\"Second-Kind\"").unwrap();
+    assert_eq!(value, LowercaseWithRename::SecondKind);
+    let value: LowercaseWithRename = serde_json::from_str("\"thirdkind\"").unwrap();
+    assert_eq!(value, LowercaseWithRename::ThirdKind);
+}
+
 // Initial test:
 #[api(
     input: {
@@ -202,6 +236,78 @@ fn string_check_schema_test() {
     assert_eq!(TEST_METHOD, API_METHOD_STRING_CHECK);
 }
 
+#[api(returns: { type: Selection })]
+/// Get the current selection.
+pub fn get_selection() -> Result<Selection, Error> {
+    Ok(Selection::AnotherKind)
+}
+
+#[api(returns: { optional: true, type: Selection })]
+/// Get the current selection if there is one.
+pub fn get_optional_selection() -> Result<Option<Selection>, Error> {
+    Ok(None)
+}
+
+#[test]
+fn api_type_return_schema_test() {
+    const TEST_METHOD: ::proxmox_router::ApiMethod = ::proxmox_router::ApiMethod::new(
+        &::proxmox_router::ApiHandler::Sync(&api_function_get_selection),
+        &::proxmox_schema::ObjectSchema::new("Get the current selection.", &[]),
+    )
+    .returns(::proxmox_schema::ReturnType::new(
+        false,
+        &Selection::API_SCHEMA,
+    ))
+    .protected(false);
+
+    assert_eq!(TEST_METHOD, API_METHOD_GET_SELECTION);
+
+    assert_eq!(
+        API_METHOD_GET_OPTIONAL_SELECTION.returns,
+        ::proxmox_schema::ReturnType::new(true, &Selection::API_SCHEMA),
+    );
+}
+
+#[api(returns: { type: null })]
+/// Reset the selection.
+pub fn reset_selection() -> Result<(), Error> {
+    Ok(())
+}
+
+#[test]
+fn explicit_null_return_test() {
+    const TEST_METHOD: ::proxmox_router::ApiMethod = ::proxmox_router::ApiMethod::new(
+        &::proxmox_router::ApiHandler::Sync(&api_function_reset_selection),
+        &::proxmox_schema::ObjectSchema::new("Reset the selection.", &[]),
+    )
+    .returns(::proxmox_schema::ReturnType::new(
+        false,
+        &::proxmox_schema::Schema::Null,
+    ))
+    .protected(false);
+
+    assert_eq!(TEST_METHOD, API_METHOD_RESET_SELECTION);
+
+    let returns =
+        |method: &::proxmox_router::ApiMethod| (method.returns_declared, method.returns.schema);
+
+    assert_eq!(
+        returns(&API_METHOD_RESET_SELECTION),
+        (true, &::proxmox_schema::Schema::Null),
+    );
+    // without a `returns` block, the same null schema is used, but it is not declared
+    assert_eq!(
+        returns(&API_METHOD_GET_UNDECLARED),
+        (false, &::proxmox_schema::Schema::Null),
+    );
+}
+
+#[api]
+/// A method without a declared return type.
+pub fn get_undeclared() -> Result<(), Error> {
+    Ok(())
+}
+
 #[api(
     properties: {
         "a-field": {
@@ -281,3 +387,42 @@ fn enum_with_other() {
         .check_constraints("any string")
         .expect("check_constraints on 'other' value failed");
 }
+
+#[api(type: Integer)]
+/// A priority level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Priority {
+    /// Low priority.
+    Low = 1,
+    /// Normal priority.
+    Normal = 5,
+    /// High priority.
+    High = 10,
+}
+
+#[test]
+fn integer_enum() {
+    const PRIORITY_SCHEMA: ::proxmox_schema::Schema =
+        ::proxmox_schema::IntegerSchema::new("A priority level.")
+            .allowed_values(&[1, 5, 10])
+            .schema();
+
+    assert_eq!(PRIORITY_SCHEMA, Priority::API_SCHEMA);
+
+    assert_eq!(serde_json::to_value(Priority::Low).unwrap(), 1);
+    assert_eq!(serde_json::to_value(Priority::Normal).unwrap(), 5);
+    assert_eq!(serde_json::to_value(Priority::High).unwrap(), 10);
+
+    let priority: Priority = serde_json::from_str("10").expect("failed to parse `Priority`");
+    assert_eq!(priority, Priority::High);
+    serde_json::from_str::<Priority>("3").expect_err("parsed invalid `Priority`");
+    serde_json::from_str::<Priority>("\"low\"").expect_err("parsed `Priority` from a string");
+
+    let schema = const { PRIORITY_SCHEMA.unwrap_integer_schema() };
+    schema
+        .check_constraints(5)
+        .expect("check_constraints on valid value failed");
+    schema
+        .check_constraints(3)
+        .expect_err("check_constraints on invalid value succeeded");
+}
@@ -0,0 +1,87 @@
+//! A small string interning pool.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+/// A pool of shared strings.
+///
+/// Interning the same string multiple times yields clones of the same `Arc<str>`, so that
+/// strings repeated across many objects (for instance the keys of a large parsed config) only
+/// need to be allocated once.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use proxmox_borrow::Interner;
+///
+/// let interner = Interner::new();
+/// let a = interner.intern("comment");
+/// let b = interner.intern(&String::from("comment"));
+/// assert!(Arc::ptr_eq(&a, &b));
+/// ```
+#[derive(Debug, Default)]
+pub struct Interner {
+    strings: Mutex<HashSet<Arc<str>>>,
+}
+
+impl Interner {
+    /// Create a new, empty pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the shared copy of `s`, adding it to the pool if it is not yet present.
+    pub fn intern(&self, s: &str) -> Arc<str> {
+        let mut strings = self.strings.lock().unwrap();
+        if let Some(existing) = strings.get(s) {
+            return Arc::clone(existing);
+        }
+
+        let new: Arc<str> = Arc::from(s);
+        strings.insert(Arc::clone(&new));
+        new
+    }
+
+    /// The number of distinct strings in the pool.
+    pub fn len(&self) -> usize {
+        self.strings.lock().unwrap().len()
+    }
+
+    /// Check whether the pool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all strings from the pool. Previously returned `Arc`s stay valid.
+    pub fn clear(&self) {
+        self.strings.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::Interner;
+
+    #[test]
+    fn test_intern_shares_allocation() {
+        let interner = Interner::new();
+        assert!(interner.is_empty());
+
+        let a = interner.intern("digest");
+        let owned = String::from("digest");
+        let b = interner.intern(&owned);
+        let c = interner.intern("comment");
+
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &c));
+        assert_eq!(&*c, "comment");
+        assert_eq!(interner.len(), 2);
+
+        interner.clear();
+        assert!(interner.is_empty());
+        assert_eq!(&*a, "digest");
+        assert!(!Arc::ptr_eq(&a, &interner.intern("digest")));
+    }
+}
@@ -4,6 +4,9 @@
 
 use std::mem::ManuallyDrop;
 
+mod interner;
+pub use interner::Interner;
+
 /// This ties two values together, so that one value can borrow from the other, while allowing the
 /// resulting object to be stored in a struct. The life time of the borrow will not infect the
 /// surrounding type's signature.
@@ -225,6 +225,87 @@ impl std::str::FromStr for HumanByte {
     }
 }
 
+/// Parse a transfer rate and normalize it to bytes per second.
+///
+/// Accepts a number followed by an optional unit and an optional `/s` suffix, for example
+/// `"500k"`, `"10MiB/s"` or `"1Gbit"`. Units follow the same SI (base 10) and IEC (base 2) rules as
+/// [`HumanByte`], a trailing `bit` denotes bits instead of bytes. Since a lowercase `b` is commonly
+/// used for both, it is rejected as ambiguous.
+pub fn parse_rate(s: &str) -> Result<u64, Error> {
+    let input = s.trim();
+    let v = input.strip_suffix("/s").unwrap_or(input);
+
+    let split = v
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(v.len());
+    let (number, unit) = (&v[..split], v[split..].trim_start());
+
+    let number: f64 = match number.parse() {
+        Ok(number) => number,
+        Err(err) => bail!("invalid rate '{input}': {err}"),
+    };
+
+    let (prefix, bits) = if let Some(prefix) = unit.strip_suffix("bit") {
+        (prefix, true)
+    } else if let Some(prefix) = unit.strip_suffix('B') {
+        (prefix, false)
+    } else if unit.ends_with('b') {
+        bail!("ambiguous unit in rate '{input}', use 'B' for bytes or 'bit' for bits");
+    } else {
+        (unit, false)
+    };
+
+    let unit = match prefix {
+        "" => SizeUnit::Byte,
+        "k" | "K" => SizeUnit::KByte,
+        "M" => SizeUnit::MByte,
+        "G" => SizeUnit::GByte,
+        "T" => SizeUnit::TByte,
+        "P" => SizeUnit::PByte,
+        "Ki" => SizeUnit::Kibi,
+        "Mi" => SizeUnit::Mebi,
+        "Gi" => SizeUnit::Gibi,
+        "Ti" => SizeUnit::Tebi,
+        "Pi" => SizeUnit::Pebi,
+        _ => bail!("unknown unit '{prefix}' in rate '{input}'"),
+    };
+
+    let mut rate = number * unit.factor();
+    if bits {
+        rate /= 8.0;
+    }
+
+    if rate > u64::MAX as f64 {
+        bail!("rate '{input}' is too large");
+    }
+
+    Ok(rate as u64)
+}
+
+/// Parse a size range of the form `<min>-<max>` and return it as `(min, max)` in bytes.
+///
+/// Both sizes are parsed like [`HumanByte`], for example `"1G-2TiB"`. A single size `v` is
+/// treated as the range `(v, v)`.
+pub fn parse_size_range(s: &str) -> Result<(u64, u64), Error> {
+    let input = s.trim();
+    let (min, max) = input.split_once('-').unwrap_or((input, input));
+
+    let min = match min.trim().parse::<HumanByte>() {
+        Ok(min) => min.as_u64(),
+        Err(err) => bail!("invalid minimum in size range '{input}': {err}"),
+    };
+    let max = match max.trim().parse::<HumanByte>() {
+        Ok(max) => max.as_u64(),
+        Err(err) => bail!("invalid maximum in size range '{input}': {err}"),
+    };
+
+    if min > max {
+        bail!("invalid size range '{input}': minimum is larger than maximum");
+    }
+
+    Ok((min, max))
+}
+
 proxmox_serde::forward_deserialize_to_from_str!(HumanByte);
 proxmox_serde::forward_serialize_to_display!(HumanByte);
 
@@ -318,6 +399,58 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_rate() -> Result<(), Error> {
+        // bare numbers
+        assert_eq!(parse_rate("0")?, 0);
+        assert_eq!(parse_rate("1500")?, 1500);
+        assert_eq!(parse_rate("1500/s")?, 1500);
+
+        // byte units
+        assert_eq!(parse_rate("500k")?, 500_000);
+        assert_eq!(parse_rate("500 KB/s")?, 500_000);
+        assert_eq!(parse_rate("10MiB/s")?, 10 * 1024 * 1024);
+        assert_eq!(parse_rate("1.5Gi")?, 3 * 512 * 1024 * 1024);
+        assert_eq!(parse_rate("2B")?, 2);
+
+        // bit units
+        assert_eq!(parse_rate("1Gbit")?, 125_000_000);
+        assert_eq!(parse_rate("100 Mbit/s")?, 12_500_000);
+        assert_eq!(parse_rate("8Kibit")?, 1024);
+        assert_eq!(parse_rate("16bit")?, 2);
+
+        // ambiguous and unknown units
+        assert!(parse_rate("10Mb").is_err());
+        assert!(parse_rate("10b").is_err());
+        assert!(parse_rate("10X").is_err());
+        assert!(parse_rate("10 MiBit").is_err());
+        assert!(parse_rate("10m").is_err());
+
+        // invalid numbers
+        assert!(parse_rate("").is_err());
+        assert!(parse_rate("-5").is_err());
+        assert!(parse_rate("1.2.3M").is_err());
+        assert!(parse_rate("MiB").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_size_range() -> Result<(), Error> {
+        assert_eq!(parse_size_range("1G-2TiB")?, (1_000_000_000, 2 << 40));
+        assert_eq!(parse_size_range("512 - 1KiB")?, (512, 1024));
+        assert_eq!(parse_size_range("1KiB-1024")?, (1024, 1024));
+        assert_eq!(parse_size_range("10MiB")?, (10 << 20, 10 << 20));
+
+        assert!(parse_size_range("2G-1G").is_err());
+        assert!(parse_size_range("1G-").is_err());
+        assert!(parse_size_range("-1G").is_err());
+        assert!(parse_size_range("1G-2G-3G").is_err());
+        assert!(parse_size_range("").is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_human_byte_auto_unit_decimal() {
         fn convert(b: u64) -> String {
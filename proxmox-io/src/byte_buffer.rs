@@ -93,6 +93,24 @@ impl ByteBuffer {
         &mut self.buf[self.data_size..self.capacity]
     }
 
+    /// Returns a view of the next `n` bytes of data without removing them
+    /// from the buffer, or `None` if fewer than `n` bytes are available.
+    ///
+    /// Example:
+    /// ```
+    /// # use proxmox_io::ByteBuffer;
+    /// let mut buf = ByteBuffer::new();
+    /// buf.get_free_mut_slice()[..2].copy_from_slice(&[1u8, 2u8]);
+    /// buf.add_size(2);
+    ///
+    /// assert_eq!(buf.peek(1), Some(&[1u8][..]));
+    /// assert_eq!(buf.peek(3), None);
+    /// assert_eq!(buf.len(), 2);
+    /// ```
+    pub fn peek(&self, n: usize) -> Option<&[u8]> {
+        self.buf[..self.data_size].get(..n)
+    }
+
     /// Removes up to max_amount of data from the front
     /// of the buffer and returns. If there was less than max_amount present,
     /// it will return as much data as there was in the buffer.
@@ -149,6 +167,46 @@ impl ByteBuffer {
         size
     }
 
+    /// Removes `N` bytes from the front of the buffer, or returns `None` if fewer are available.
+    fn take_array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let bytes: [u8; N] = self.peek(N)?.try_into().ok()?;
+        self.consume(N);
+        Some(bytes)
+    }
+
+    /// Reads a big-endian `u16` from the front of the buffer.
+    ///
+    /// Returns `None` and leaves the buffer untouched if fewer than 2 bytes are available.
+    pub fn read_be_u16(&mut self) -> Option<u16> {
+        self.take_array().map(u16::from_be_bytes)
+    }
+
+    /// Reads a big-endian `u32` from the front of the buffer.
+    ///
+    /// Returns `None` and leaves the buffer untouched if fewer than 4 bytes are available.
+    ///
+    /// Example:
+    /// ```
+    /// # use proxmox_io::ByteBuffer;
+    /// let mut buf = ByteBuffer::new();
+    /// buf.get_free_mut_slice()[..5].copy_from_slice(&[0, 0, 1, 2, 3]);
+    /// buf.add_size(5);
+    ///
+    /// assert_eq!(buf.read_be_u32(), Some(0x0102));
+    /// assert_eq!(buf.read_be_u32(), None);
+    /// assert_eq!(buf.len(), 1);
+    /// ```
+    pub fn read_be_u32(&mut self) -> Option<u32> {
+        self.take_array().map(u32::from_be_bytes)
+    }
+
+    /// Reads a big-endian `u64` from the front of the buffer.
+    ///
+    /// Returns `None` and leaves the buffer untouched if fewer than 8 bytes are available.
+    pub fn read_be_u64(&mut self) -> Option<u64> {
+        self.take_array().map(u64::from_be_bytes)
+    }
+
     /// Takes a reader and reads into the back of the buffer (up to the
     /// free space in the buffer) and updates its size accordingly.
     ///
@@ -224,4 +282,53 @@ mod test {
         assert_eq!(buffer.len(), size);
         assert_eq!(buffer[0], 54);
     }
+
+    #[test]
+    fn test_peek() {
+        let mut buffer = ByteBuffer::with_capacity(16);
+        assert_eq!(buffer.peek(0), Some(&[][..]));
+        assert_eq!(buffer.peek(1), None);
+
+        buffer.get_free_mut_slice()[..4].copy_from_slice(&[1, 2, 3, 4]);
+        buffer.add_size(4);
+
+        assert_eq!(buffer.peek(5), None);
+        assert_eq!(buffer.peek(16), None);
+        assert_eq!(buffer.peek(4), Some(&[1, 2, 3, 4][..]));
+
+        assert_eq!(buffer.peek(2), Some(&[1, 2][..]));
+        assert_eq!(buffer.len(), 4);
+        assert_eq!(buffer.consume(2), 2);
+        assert_eq!(buffer.peek(2), Some(&[3, 4][..]));
+        assert_eq!(buffer.peek(3), None);
+    }
+
+    #[test]
+    fn test_read_be() {
+        let mut buffer = ByteBuffer::with_capacity(32);
+        assert_eq!(buffer.read_be_u16(), None);
+
+        let data = [
+            0x01, 0x02, // u16
+            0x03, 0x04, 0x05, 0x06, // u32
+            0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, // u64
+            0xff, 0xfe, 0xfd, // leftover
+        ];
+        buffer.get_free_mut_slice()[..data.len()].copy_from_slice(&data);
+        buffer.add_size(data.len());
+
+        assert_eq!(buffer.read_be_u16(), Some(0x0102));
+        assert_eq!(buffer.read_be_u32(), Some(0x03040506));
+        assert_eq!(buffer.read_be_u64(), Some(0x0708090a0b0c0d0e));
+        assert_eq!(buffer.len(), 3);
+
+        // insufficient data leaves the buffer untouched
+        assert_eq!(buffer.read_be_u64(), None);
+        assert_eq!(buffer.read_be_u32(), None);
+        assert_eq!(&buffer[..], &[0xff, 0xfe, 0xfd]);
+
+        assert_eq!(buffer.read_be_u16(), Some(0xfffe));
+        assert_eq!(buffer.read_be_u16(), None);
+        assert_eq!(buffer.len(), 1);
+    }
 }
@@ -0,0 +1,95 @@
+//! Helpers for length-prefixed frames.
+//!
+//! A frame consists of a big-endian `u32` length followed by exactly that many bytes of data.
+
+use std::io::{self, Read, Write};
+
+use crate::ReadExt;
+
+/// Read a length-prefixed frame.
+///
+/// Frames larger than `max` bytes are rejected with an [`InvalidData`](io::ErrorKind::InvalidData)
+/// error before any of their data is read.
+///
+/// Returns `Ok(None)` if the reader is at EOF before the start of a frame. Running into EOF in the
+/// middle of a frame results in an [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) error.
+pub fn read_frame<R: Read>(mut reader: R, max: usize) -> io::Result<Option<Vec<u8>>> {
+    let mut length = [0u8; 4];
+    let mut have = 0;
+    while have < length.len() {
+        match reader.read(&mut length[have..]) {
+            Ok(0) if have == 0 => return Ok(None),
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(got) => have += got,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+
+    let length = u32::from_be_bytes(length) as usize;
+    if length > max {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame size {length} exceeds limit of {max} bytes"),
+        ));
+    }
+
+    reader.read_exact_allocated(length).map(Some)
+}
+
+/// Write `data` as a length-prefixed frame.
+///
+/// Fails with an [`InvalidInput`](io::ErrorKind::InvalidInput) error if the data is too large for
+/// a `u32` length prefix.
+pub fn write_frame<W: Write>(mut writer: W, data: &[u8]) -> io::Result<()> {
+    let length = u32::try_from(data.len()).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("frame size {} too large", data.len()),
+        )
+    })?;
+
+    writer.write_all(&length.to_be_bytes())?;
+    writer.write_all(data)
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{self, Cursor};
+
+    use super::{read_frame, write_frame};
+
+    #[test]
+    fn test_frame_round_trip() {
+        let mut buffer = Vec::new();
+        write_frame(&mut buffer, b"first frame").unwrap();
+        write_frame(&mut buffer, b"").unwrap();
+        assert_eq!(&buffer[..4], &11u32.to_be_bytes());
+
+        let mut reader = Cursor::new(buffer);
+        assert_eq!(
+            read_frame(&mut reader, 64).unwrap().as_deref(),
+            Some(&b"first frame"[..])
+        );
+        assert_eq!(
+            read_frame(&mut reader, 64).unwrap().as_deref(),
+            Some(&[][..])
+        );
+        assert!(read_frame(&mut reader, 64).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_frame_limits() {
+        let mut buffer = Vec::new();
+        write_frame(&mut buffer, &[0u8; 65]).unwrap();
+
+        let err = read_frame(Cursor::new(&buffer), 64).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let err = read_frame(Cursor::new(&buffer[..10]), 128).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        let err = read_frame(Cursor::new(&buffer[..2]), 128).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,146 @@
+//! A writer computing a digest of the data passing through it.
+
+use std::io::{self, Write};
+
+/// A digest which can be fed data incrementally, used by [`HashingWriter`].
+pub trait Digest {
+    /// The type of the final digest value.
+    type Output;
+
+    /// Feed more data into the digest.
+    fn update(&mut self, data: &[u8]);
+
+    /// Finalize the digest.
+    fn finish(self) -> Self::Output;
+}
+
+#[cfg(feature = "openssl")]
+impl Digest for openssl::sha::Sha256 {
+    type Output = [u8; 32];
+
+    fn update(&mut self, data: &[u8]) {
+        openssl::sha::Sha256::update(self, data)
+    }
+
+    fn finish(self) -> [u8; 32] {
+        openssl::sha::Sha256::finish(self)
+    }
+}
+
+#[cfg(feature = "openssl")]
+impl Digest for openssl::sha::Sha512 {
+    type Output = [u8; 64];
+
+    fn update(&mut self, data: &[u8]) {
+        openssl::sha::Sha512::update(self, data)
+    }
+
+    fn finish(self) -> [u8; 64] {
+        openssl::sha::Sha512::finish(self)
+    }
+}
+
+/// Writer passing data on to an inner writer while updating a digest with it.
+///
+/// Only the data actually accepted by the inner writer is hashed, so after all data has been
+/// written successfully the digest covers exactly the written contents.
+pub struct HashingWriter<W, H> {
+    inner: W,
+    digest: H,
+}
+
+impl<W: Write, H: Digest> HashingWriter<W, H> {
+    /// Wrap a writer, updating `digest` with everything written to it.
+    pub fn new(inner: W, digest: H) -> Self {
+        Self { inner, digest }
+    }
+
+    /// Get a reference to the inner writer.
+    pub fn inner(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get the inner writer and the finalized digest.
+    pub fn finish(self) -> (W, H::Output) {
+        (self.inner, self.digest.finish())
+    }
+}
+
+impl<W: Write, H: Digest> Write for HashingWriter<W, H> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.digest.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{self, Write};
+
+    use super::{Digest, HashingWriter};
+
+    /// Simple byte sum, enough to check which data got hashed.
+    #[derive(Default)]
+    struct Sum(u64, usize);
+
+    impl Digest for Sum {
+        type Output = (u64, usize);
+
+        fn update(&mut self, data: &[u8]) {
+            self.0 += data.iter().map(|b| u64::from(*b)).sum::<u64>();
+            self.1 += data.len();
+        }
+
+        fn finish(self) -> (u64, usize) {
+            (self.0, self.1)
+        }
+    }
+
+    /// Writer accepting at most 3 bytes per call.
+    struct Short(Vec<u8>);
+
+    impl Write for Short {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let len = buf.len().min(3);
+            self.0.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_hashing_writer_short_writes() {
+        let mut writer = HashingWriter::new(Short(Vec::new()), Sum::default());
+        assert_eq!(writer.write(b"abcdef").unwrap(), 3);
+        writer.write_all(b"0123456789").unwrap();
+        assert_eq!(writer.inner().0, b"abc0123456789");
+
+        let (inner, digest) = writer.finish();
+        let mut expected = Sum::default();
+        expected.update(&inner.0);
+        assert_eq!(digest, expected.finish());
+    }
+
+    #[cfg(feature = "openssl")]
+    #[test]
+    fn test_hashing_writer_sha256() {
+        let data: Vec<u8> = (0..10_000u32).map(|i| i as u8).collect();
+
+        let mut writer = HashingWriter::new(Vec::new(), openssl::sha::Sha256::new());
+        for chunk in data.chunks(777) {
+            writer.write_all(chunk).unwrap();
+        }
+        let (buffer, digest) = writer.finish();
+
+        assert_eq!(buffer, data);
+        assert_eq!(digest, openssl::sha::sha256(&data));
+    }
+}
@@ -27,5 +27,20 @@ pub use std_channel_writer::StdChannelWriter;
 mod byte_buffer;
 pub use byte_buffer::ByteBuffer;
 
+mod frame;
+pub use frame::{read_frame, write_frame};
+
+mod tee_writer;
+pub use tee_writer::TeeWriter;
+
+mod limited_reader;
+pub use limited_reader::LimitedReader;
+
+mod read_until;
+pub use read_until::read_until_limited;
+
+mod hashing_writer;
+pub use hashing_writer::{Digest, HashingWriter};
+
 pub mod boxed;
 pub mod vec;
@@ -0,0 +1,120 @@
+//! A reader enforcing an upper limit on the amount of data read.
+
+use std::io::{self, Read};
+
+/// Reader passing data through from an inner reader, failing once more than `limit` bytes are
+/// available.
+///
+/// Reading exactly `limit` bytes followed by EOF succeeds. As soon as the inner reader yields any
+/// data beyond the limit, an [`InvalidData`](io::ErrorKind::InvalidData) error is returned. This
+/// is useful to guard against oversized input, e.g. when receiving data from remote peers.
+///
+/// ```
+/// # use std::io::Read;
+/// # use proxmox_io::LimitedReader;
+/// let mut reader = LimitedReader::new(&b"too much data"[..], 8);
+/// let mut buffer = Vec::new();
+/// assert!(reader.read_to_end(&mut buffer).is_err());
+/// assert_eq!(reader.count(), 8);
+/// ```
+pub struct LimitedReader<R> {
+    inner: R,
+    limit: u64,
+    count: u64,
+}
+
+impl<R: Read> LimitedReader<R> {
+    /// Wrap `inner`, allowing at most `limit` bytes to be read.
+    pub fn new(inner: R, limit: u64) -> Self {
+        Self {
+            inner,
+            limit,
+            count: 0,
+        }
+    }
+
+    /// Get the number of bytes read so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Get the configured limit.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Get the inner reader back.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // allow reading one byte past the limit to be able to tell it was exceeded
+        let allowed = (self.limit - self.count).saturating_add(1);
+        let max = usize::try_from(allowed).map_or(buf.len(), |allowed| buf.len().min(allowed));
+
+        let got = self.inner.read(&mut buf[..max])?;
+        if got as u64 > self.limit - self.count {
+            self.count = self.limit;
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("read limit of {} bytes exceeded", self.limit),
+            ));
+        }
+
+        self.count += got as u64;
+        Ok(got)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{self, Read};
+
+    use super::LimitedReader;
+
+    fn read_all(data: &[u8], limit: u64) -> (io::Result<Vec<u8>>, u64) {
+        let mut reader = LimitedReader::new(data, limit);
+        let mut buffer = Vec::new();
+        let result = reader.read_to_end(&mut buffer).map(|_| buffer);
+        (result, reader.count())
+    }
+
+    #[test]
+    fn test_limited_reader() {
+        let data = b"0123456789";
+
+        // under the limit
+        let (result, count) = read_all(data, 16);
+        assert_eq!(result.unwrap(), data);
+        assert_eq!(count, 10);
+
+        // exactly at the limit
+        let (result, count) = read_all(data, 10);
+        assert_eq!(result.unwrap(), data);
+        assert_eq!(count, 10);
+
+        // over the limit
+        let (result, count) = read_all(data, 9);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(count, 9);
+
+        let (result, _) = read_all(data, 0);
+        assert!(result.is_err());
+        let (result, _) = read_all(b"", 0);
+        assert!(result.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_limited_reader_small_reads() {
+        let mut reader = LimitedReader::new(&b"abcdef"[..], 4);
+        let mut buffer = [0u8; 3];
+        assert_eq!(reader.read(&mut buffer).unwrap(), 3);
+        assert_eq!(reader.count(), 3);
+        assert_eq!(reader.read(&mut buffer[..1]).unwrap(), 1);
+        assert_eq!(reader.count(), 4);
+        assert!(reader.read(&mut buffer).is_err());
+    }
+}
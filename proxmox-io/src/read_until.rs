@@ -0,0 +1,103 @@
+//! Bounded version of [`BufRead::read_until`].
+
+use std::io::{self, BufRead};
+
+/// Read from `reader` up to and including the delimiter `delim`.
+///
+/// Fails with an [`InvalidData`](io::ErrorKind::InvalidData) error if `max` bytes have been read
+/// without finding the delimiter, so the returned data, including the delimiter, is never longer
+/// than `max` bytes. No data past the limit is consumed from the reader.
+///
+/// Like [`BufRead::read_until`], reaching EOF before the delimiter returns the data read so far,
+/// which is empty at the end of the input.
+///
+/// ```
+/// # use proxmox_io::read_until_limited;
+/// let mut input = &b"first\nsecond line\n"[..];
+/// assert_eq!(read_until_limited(&mut input, b'\n', 8).unwrap(), b"first\n");
+/// assert!(read_until_limited(&mut input, b'\n', 8).is_err());
+/// ```
+pub fn read_until_limited<R: BufRead + ?Sized>(
+    reader: &mut R,
+    delim: u8,
+    max: usize,
+) -> io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    loop {
+        let available = match reader.fill_buf() {
+            Ok(available) => available,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+
+        if available.is_empty() {
+            return Ok(data);
+        }
+
+        let remaining = max - data.len();
+        let window = &available[..available.len().min(remaining)];
+        if let Some(pos) = window.iter().position(|&b| b == delim) {
+            data.extend_from_slice(&window[..=pos]);
+            reader.consume(pos + 1);
+            return Ok(data);
+        }
+
+        let used = window.len();
+        data.extend_from_slice(window);
+        reader.consume(used);
+
+        if data.len() == max {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("no delimiter found within {max} bytes"),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{self, BufRead, BufReader};
+
+    use super::read_until_limited;
+
+    #[test]
+    fn test_read_until_limited() {
+        let mut input = &b"one\ntwo\nthree"[..];
+        assert_eq!(read_until_limited(&mut input, b'\n', 4).unwrap(), b"one\n");
+        assert_eq!(read_until_limited(&mut input, b'\n', 4).unwrap(), b"two\n");
+        // EOF without a delimiter
+        assert_eq!(read_until_limited(&mut input, b'\n', 16).unwrap(), b"three");
+        assert!(
+            read_until_limited(&mut input, b'\n', 16)
+                .unwrap()
+                .is_empty()
+        );
+
+        // lines may span multiple buffer fills
+        let mut reader = BufReader::with_capacity(2, &b"abcdef\nrest"[..]);
+        assert_eq!(
+            read_until_limited(&mut reader, b'\n', 7).unwrap(),
+            b"abcdef\n"
+        );
+        let mut rest = Vec::new();
+        reader.read_until(b'\n', &mut rest).unwrap();
+        assert_eq!(rest, b"rest");
+    }
+
+    #[test]
+    fn test_read_until_limited_exceeded() {
+        let mut input = &b"abcdef\n"[..];
+        let err = read_until_limited(&mut input, b'\n', 6).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        // nothing past the limit is consumed
+        assert_eq!(input, b"\n");
+
+        let mut reader = BufReader::with_capacity(3, &b"abcdefgh"[..]);
+        let err = read_until_limited(&mut reader, b'\n', 4).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let mut input = &b"\n"[..];
+        assert!(read_until_limited(&mut input, b'\n', 0).is_err());
+    }
+}
@@ -0,0 +1,125 @@
+//! A writer duplicating its output to multiple sinks.
+
+use std::io::{self, Write};
+
+/// Writer passing all data on to each of its sinks, e.g. to log to both a file and the journal.
+///
+/// Every write is forwarded to all sinks in order via [`write_all`](Write::write_all), so all
+/// sinks always receive the same data. If a sink fails, the error reports the index of the
+/// failing sink, sinks after it have not received the data in this case.
+#[derive(Default)]
+pub struct TeeWriter {
+    sinks: Vec<Box<dyn Write + Send>>,
+}
+
+impl TeeWriter {
+    /// Create a writer without any sinks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a writer from a list of sinks.
+    pub fn from_sinks(sinks: Vec<Box<dyn Write + Send>>) -> Self {
+        Self { sinks }
+    }
+
+    /// Add a sink.
+    pub fn add_sink<W: Write + Send + 'static>(&mut self, sink: W) {
+        self.sinks.push(Box::new(sink));
+    }
+
+    /// Add a sink, builder style.
+    pub fn sink<W: Write + Send + 'static>(mut self, sink: W) -> Self {
+        self.add_sink(sink);
+        self
+    }
+
+    /// Get the sinks back.
+    pub fn into_sinks(self) -> Vec<Box<dyn Write + Send>> {
+        self.sinks
+    }
+}
+
+fn sink_error(index: usize, err: io::Error) -> io::Error {
+    io::Error::new(
+        err.kind(),
+        format!("tee writer sink {index} failed - {err}"),
+    )
+}
+
+impl Write for TeeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for (index, sink) in self.sinks.iter_mut().enumerate() {
+            sink.write_all(buf).map_err(|err| sink_error(index, err))?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        for (index, sink) in self.sinks.iter_mut().enumerate() {
+            sink.flush().map_err(|err| sink_error(index, err))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    use super::TeeWriter;
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    struct FailingSink;
+
+    impl Write for FailingSink {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "sink closed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_tee_writer() {
+        let first = SharedBuffer::default();
+        let second = SharedBuffer::default();
+
+        let mut writer = TeeWriter::new().sink(first.clone()).sink(second.clone());
+        writer.write_all(b"hello ").unwrap();
+        writeln!(writer, "world").unwrap();
+        writer.flush().unwrap();
+
+        assert_eq!(&first.0.lock().unwrap()[..], b"hello world\n");
+        assert_eq!(&second.0.lock().unwrap()[..], b"hello world\n");
+    }
+
+    #[test]
+    fn test_tee_writer_failing_sink() {
+        let first = SharedBuffer::default();
+        let mut writer = TeeWriter::new().sink(first.clone()).sink(FailingSink);
+
+        let err = writer.write_all(b"data").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+        assert!(
+            err.to_string().contains("sink 1"),
+            "unexpected error: {err}"
+        );
+        assert_eq!(&first.0.lock().unwrap()[..], b"data");
+    }
+}
@@ -84,3 +84,183 @@ pub fn zeroed(len: usize) -> Vec<u8> {
 pub fn undefined(len: usize) -> Vec<u8> {
     zeroed(len)
 }
+
+/// Remove consecutive repeated elements like [`Vec::dedup`], returning the number of removed
+/// elements.
+///
+/// ```
+/// let mut list = vec![1, 1, 2, 3, 3, 3, 1];
+/// assert_eq!(proxmox_io::vec::dedup_adjacent(&mut list), 3);
+/// assert_eq!(list, [1, 2, 3, 1]);
+/// ```
+pub fn dedup_adjacent<T: PartialEq>(vec: &mut Vec<T>) -> usize {
+    let len = vec.len();
+    vec.dedup();
+    len - vec.len()
+}
+
+/// Retain only the elements for which `keep` returns `true`, like [`Vec::retain`], but also pass
+/// each element's original index to the predicate.
+///
+/// The predicate is called exactly once for every element, in order.
+///
+/// ```
+/// let mut list = vec!["a", "b", "c", "d"];
+/// proxmox_io::vec::retain_indexed(&mut list, |index, _| index % 2 == 1);
+/// assert_eq!(list, ["b", "d"]);
+/// ```
+pub fn retain_indexed<T, F>(vec: &mut Vec<T>, mut keep: F)
+where
+    F: FnMut(usize, &T) -> bool,
+{
+    let mut index = 0;
+    vec.retain(|elem| {
+        let keep = keep(index, elem);
+        index += 1;
+        keep
+    });
+}
+
+/// Insert `item` into the sorted `vec`, keeping it sorted, and return its index.
+///
+/// The insertion point is found via binary search. Equal elements keep their insertion order, the
+/// new item is placed after them.
+///
+/// ```
+/// let mut list = vec![1, 3, 5];
+/// assert_eq!(proxmox_io::vec::insert_sorted(&mut list, 4), 2);
+/// assert_eq!(list, [1, 3, 4, 5]);
+/// ```
+pub fn insert_sorted<T: Ord>(vec: &mut Vec<T>, item: T) -> usize {
+    insert_sorted_by(vec, item, T::cmp)
+}
+
+/// Like [`insert_sorted`], with `vec` being sorted by the comparator function `compare`.
+pub fn insert_sorted_by<T, F>(vec: &mut Vec<T>, item: T, mut compare: F) -> usize
+where
+    F: FnMut(&T, &T) -> std::cmp::Ordering,
+{
+    let index = vec.partition_point(|elem| compare(elem, &item) != std::cmp::Ordering::Greater);
+    vec.insert(index, item);
+    index
+}
+
+/// Like [`insert_sorted`], with `vec` being sorted by the key extraction function `key`.
+pub fn insert_sorted_by_key<T, K, F>(vec: &mut Vec<T>, item: T, mut key: F) -> usize
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    insert_sorted_by(vec, item, |a, b| key(a).cmp(&key(b)))
+}
+
+/// View `slice` as a slice of fixed size chunks of `N` elements, e.g. to access a blob of packed
+/// 32 byte digests.
+///
+/// Fails with an [`InvalidData`](std::io::ErrorKind::InvalidData) error if the length of `slice`
+/// is not a multiple of `N`.
+///
+/// ```
+/// let blob = [0u8; 64];
+/// let digests: &[[u8; 32]] = proxmox_io::vec::chunk_exact(&blob).unwrap();
+/// assert_eq!(digests.len(), 2);
+///
+/// assert!(proxmox_io::vec::chunk_exact::<u8, 32>(&blob[..63]).is_err());
+/// ```
+pub fn chunk_exact<T, const N: usize>(slice: &[T]) -> std::io::Result<&[[T; N]]> {
+    const { assert!(N != 0, "chunk size must not be zero") };
+
+    if !slice.len().is_multiple_of(N) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "length {} is not a multiple of the chunk size {N}",
+                slice.len()
+            ),
+        ));
+    }
+
+    // SAFETY: `[T; N]` has the same layout as `N` consecutive `T`s, and the length was checked to
+    // be a multiple of `N` above.
+    Ok(unsafe { std::slice::from_raw_parts(slice.as_ptr().cast(), slice.len() / N) })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        chunk_exact, dedup_adjacent, insert_sorted, insert_sorted_by, insert_sorted_by_key,
+        retain_indexed,
+    };
+
+    #[test]
+    fn test_dedup_adjacent() {
+        let mut list = vec!["a", "a", "b", "c", "c", "a", "a", "a"];
+        assert_eq!(dedup_adjacent(&mut list), 4);
+        assert_eq!(list, ["a", "b", "c", "a"]);
+
+        let mut unique = vec![1, 2, 3];
+        assert_eq!(dedup_adjacent(&mut unique), 0);
+        assert_eq!(unique, [1, 2, 3]);
+
+        let mut empty: Vec<u8> = Vec::new();
+        assert_eq!(dedup_adjacent(&mut empty), 0);
+    }
+
+    #[test]
+    fn test_retain_indexed() {
+        let mut list: Vec<u32> = (10..20).collect();
+        retain_indexed(&mut list, |index, _| index % 2 != 0);
+        assert_eq!(list, [11, 13, 15, 17, 19]);
+
+        let mut list: Vec<u32> = (10..20).collect();
+        retain_indexed(&mut list, |index, _| (3..6).contains(&index));
+        assert_eq!(list, [13, 14, 15]);
+
+        // indices refer to the original positions, even after removing elements
+        let mut list = vec!["x", "a", "x", "b"];
+        let mut seen = Vec::new();
+        retain_indexed(&mut list, |index, elem| {
+            seen.push(index);
+            *elem != "x"
+        });
+        assert_eq!(list, ["a", "b"]);
+        assert_eq!(seen, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_insert_sorted() {
+        let mut list = Vec::new();
+        assert_eq!(insert_sorted(&mut list, 5), 0);
+        assert_eq!(insert_sorted(&mut list, 1), 0);
+        assert_eq!(insert_sorted(&mut list, 3), 1);
+        assert_eq!(insert_sorted(&mut list, 9), 3);
+        assert_eq!(insert_sorted(&mut list, 3), 2);
+        assert_eq!(list, [1, 3, 3, 5, 9]);
+
+        let mut reversed = vec![9, 5, 1];
+        assert_eq!(insert_sorted_by(&mut reversed, 7, |a, b| b.cmp(a)), 1);
+        assert_eq!(reversed, [9, 7, 5, 1]);
+
+        let mut entries = vec![("a", 1), ("c", 3)];
+        assert_eq!(insert_sorted_by_key(&mut entries, ("b", 2), |e| e.1), 1);
+        assert_eq!(insert_sorted_by_key(&mut entries, ("d", 2), |e| e.1), 2);
+        assert_eq!(entries, [("a", 1), ("b", 2), ("d", 2), ("c", 3)]);
+    }
+
+    #[test]
+    fn test_chunk_exact() {
+        let data: Vec<u8> = (0..96).collect();
+        let chunks: &[[u8; 32]] = chunk_exact(&data).unwrap();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0][0], 0);
+        assert_eq!(chunks[1][0], 32);
+        assert_eq!(chunks[2][31], 95);
+
+        let empty: &[[u8; 32]] = chunk_exact(&data[..0]).unwrap();
+        assert!(empty.is_empty());
+
+        let err = chunk_exact::<u8, 32>(&data[..95]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(chunk_exact::<u8, 32>(&data[..16]).is_err());
+    }
+}
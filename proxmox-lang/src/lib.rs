@@ -9,6 +9,7 @@
 mod constnamedbitmap;
 
 pub mod error;
+pub mod named_bitmap;
 pub mod ops;
 
 /// Macro to write error-handling blocks (like perl eval {})
@@ -36,6 +37,35 @@ macro_rules! try_block {
     { $($token:tt)* } => {{ (|| -> Result<_,_> { $($token)* })() }}
 }
 
+/// Like [`try_block!`], but annotates errors with the source location of the block.
+///
+/// The block must return an `anyhow::Result`, and the error is wrapped via `anyhow::Context`, so
+/// the calling crate needs to depend on `anyhow`.
+///
+/// #### Example:
+/// ```
+/// # use anyhow::bail;
+/// # use proxmox_lang::try_block_at;
+/// let result: Result<(), _> = try_block_at!({
+///     bail!("some error");
+/// });
+///
+/// let err = result.unwrap_err();
+/// assert_eq!(err.root_cause().to_string(), "some error");
+/// let message = format!("{err:#}");
+/// assert!(message.starts_with(&format!("error in try block at {}:", file!())));
+/// assert!(message.ends_with(": some error"));
+/// ```
+#[macro_export]
+macro_rules! try_block_at {
+    { $($token:tt)* } => {{
+        ::anyhow::Context::with_context(
+            (|| -> ::std::result::Result<_, ::anyhow::Error> { $($token)* })(),
+            || format!("error in try block at {}:{}:{}", file!(), line!(), column!()),
+        )
+    }}
+}
+
 /// Statically assert the size of a type at compile time.
 ///
 /// This should compile:
@@ -66,6 +96,77 @@ macro_rules! static_assert_size {
     };
 }
 
+/// Statically assert the size of a type at compile time, with a readable error message.
+///
+/// Unlike [`static_assert_size!`], a mismatch is reported via a failed constant evaluation of
+/// `SizeAssert::<ACTUAL, EXPECTED>`, so the compiler output names both the actual and the
+/// expected size.
+///
+/// This should compile:
+/// ```
+/// # use proxmox_lang::const_assert_size_eq;
+/// #[repr(C)]
+/// struct Stuff {
+///     value: [u8; 32]
+/// }
+/// const_assert_size_eq!(Stuff, 32);
+/// ```
+///
+/// This should fail to compile:
+/// ```compile_fail
+/// # use proxmox_lang::const_assert_size_eq;
+/// #[repr(C)]
+/// struct Stuff {
+///     value: [u8; 32]
+/// }
+/// const_assert_size_eq!(Stuff, 128);
+/// ```
+#[macro_export]
+macro_rules! const_assert_size_eq {
+    ($ty:ty, $size:expr) => {
+        const _: () = $crate::SizeAssert::<{ ::std::mem::size_of::<$ty>() }, { $size }>::EQUAL;
+    };
+}
+
+/// Helper for [`const_assert_size_eq!`].
+#[doc(hidden)]
+pub struct SizeAssert<const ACTUAL: usize, const EXPECTED: usize>;
+
+impl<const ACTUAL: usize, const EXPECTED: usize> SizeAssert<ACTUAL, EXPECTED> {
+    pub const EQUAL: () = assert!(
+        ACTUAL == EXPECTED,
+        "type size mismatch, see `SizeAssert::<ACTUAL, EXPECTED>` for the sizes"
+    );
+}
+
+/// Get the size in bytes of a struct field.
+///
+/// This works without an instance of the struct and can be used in `const` contexts.
+///
+/// ```
+/// # use proxmox_lang::field_size_of;
+/// #[repr(C)]
+/// struct Stuff {
+///     kind: u16,
+///     value: [u8; 32],
+/// }
+///
+/// assert_eq!(field_size_of!(Stuff, value), 32);
+/// const KIND_SIZE: usize = field_size_of!(Stuff, kind);
+/// assert_eq!(KIND_SIZE, 2);
+/// ```
+#[macro_export]
+macro_rules! field_size_of {
+    ($ty:ty, $field:ident) => {{
+        const fn size_of_pointee<T>(_: *const T) -> usize {
+            ::std::mem::size_of::<T>()
+        }
+        let uninit = ::std::mem::MaybeUninit::<$ty>::uninit();
+        // SAFETY: only the address of the field is computed, the memory is never read
+        size_of_pointee(unsafe { ::std::ptr::addr_of!((*uninit.as_ptr()).$field) })
+    }};
+}
+
 /// Shortcut for generating an `&'static CStr`.
 ///
 /// This takes a *string* (*not* a *byte-string*), appends a terminating zero, and calls
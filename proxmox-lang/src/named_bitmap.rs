@@ -0,0 +1,178 @@
+//! Helpers to convert between bit masks and the names of their bits, as defined via
+//! [`constnamedbitmap!`](crate::constnamedbitmap).
+
+use std::fmt;
+
+/// Error returned by [`parse_mask`] for names not present in the bitmap.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnknownBitName(pub String);
+
+impl fmt::Display for UnknownBitName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown name '{}' in bitmap", self.0)
+    }
+}
+
+impl std::error::Error for UnknownBitName {}
+
+/// Error returned by [`validate_mask`] for masks containing bits not present in the bitmap.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnknownBits(pub u64);
+
+impl UnknownBits {
+    /// Iterate over the positions of the unknown bits, starting at the least significant one.
+    pub fn positions(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..u64::BITS).filter(|pos| self.0 & (1 << pos) != 0)
+    }
+}
+
+impl fmt::Display for UnknownBits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("unknown bits in mask: ")?;
+        for (i, pos) in self.positions().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{pos}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnknownBits {}
+
+/// Iterate over the names of all bits of `bitmap` which are set in `mask`, in the order of the
+/// bitmap.
+pub fn iter_names<'a>(bitmap: &'a [(&'a str, u64)], mask: u64) -> impl Iterator<Item = &'a str> {
+    bitmap
+        .iter()
+        .filter(move |(_, bit)| mask & bit != 0)
+        .map(|(name, _)| *name)
+}
+
+/// Build a mask from a list of bit names.
+pub fn parse_mask<I, S>(bitmap: &[(&str, u64)], names: I) -> Result<u64, UnknownBitName>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    names.into_iter().try_fold(0, |mask, name| {
+        let name = name.as_ref();
+        match bitmap.iter().find(|(bit_name, _)| *bit_name == name) {
+            Some((_, bit)) => Ok(mask | bit),
+            None => Err(UnknownBitName(name.to_string())),
+        }
+    })
+}
+
+/// Check that `mask` only contains bits defined in `bitmap`.
+///
+/// This guards against stale masks, e.g. loaded from a configuration written before a bit was
+/// removed from the bitmap.
+pub fn validate_mask(bitmap: &[(&str, u64)], mask: u64) -> Result<(), UnknownBits> {
+    let known = bitmap.iter().fold(0, |known, (_, bit)| known | bit);
+    match mask & !known {
+        0 => Ok(()),
+        unknown => Err(UnknownBits(unknown)),
+    }
+}
+
+/// Generate a module usable with `#[serde(with = "...")]` which (de)serializes a `u64` mask of a
+/// [`constnamedbitmap!`](crate::constnamedbitmap) as a list of bit names.
+///
+/// The generated code uses the `serde` crate, which needs to be a dependency of the calling crate.
+///
+/// ```
+/// # use proxmox_lang::{constnamedbitmap, named_bitmap_serde};
+/// # use serde::{Deserialize, Serialize};
+/// constnamedbitmap! {
+///     /// Access flags.
+///     FLAGS: u64 => {
+///         FLAG_READ("read");
+///         FLAG_WRITE("write");
+///         FLAG_EXEC("exec");
+///     }
+/// }
+///
+/// named_bitmap_serde!(mod flags_as_names = FLAGS);
+///
+/// #[derive(Debug, PartialEq, Deserialize, Serialize)]
+/// struct Entry {
+///     #[serde(with = "flags_as_names")]
+///     flags: u64,
+/// }
+///
+/// # fn main() {
+/// let entry = Entry { flags: FLAG_READ | FLAG_WRITE };
+/// let json = serde_json::to_string(&entry).unwrap();
+/// assert_eq!(json, r#"{"flags":["read","write"]}"#);
+///
+/// let parsed: Entry = serde_json::from_str(&json).unwrap();
+/// assert_eq!(parsed, entry);
+///
+/// assert!(serde_json::from_str::<Entry>(r#"{"flags":["read","delete"]}"#).is_err());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! named_bitmap_serde {
+    ($vis:vis mod $module:ident = $bitmap:ident) => {
+        $vis mod $module {
+            pub fn serialize<S>(mask: &u64, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+            {
+                serializer.collect_seq($crate::named_bitmap::iter_names(super::$bitmap, *mask))
+            }
+
+            pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+            {
+                let names: Vec<::std::borrow::Cow<'de, str>> =
+                    ::serde::Deserialize::deserialize(deserializer)?;
+                $crate::named_bitmap::parse_mask(super::$bitmap, names)
+                    .map_err(::serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::{UnknownBitName, UnknownBits, iter_names, parse_mask, validate_mask};
+
+    crate::constnamedbitmap! {
+        BITS: u64 => {
+            BIT_A("a");
+            BIT_B("b");
+            BIT_C("c");
+        }
+    }
+
+    #[test]
+    fn test_names_and_masks() {
+        assert_eq!(iter_names(BITS, 0).count(), 0);
+        assert_eq!(
+            iter_names(BITS, BIT_C | BIT_A).collect::<Vec<_>>(),
+            ["a", "c"]
+        );
+
+        assert_eq!(parse_mask(BITS, ["b", "a"]), Ok(BIT_A | BIT_B));
+        assert_eq!(parse_mask(BITS, Vec::<String>::new()), Ok(0));
+        assert_eq!(
+            parse_mask(BITS, ["a", "x"]),
+            Err(UnknownBitName("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_validate_mask() {
+        assert_eq!(validate_mask(BITS, 0), Ok(()));
+        assert_eq!(validate_mask(BITS, BIT_A | BIT_B | BIT_C), Ok(()));
+
+        let err = validate_mask(BITS, BIT_B | 1 << 3 | 1 << 63).unwrap_err();
+        assert_eq!(err, UnknownBits(1 << 3 | 1 << 63));
+        assert_eq!(err.positions().collect::<Vec<_>>(), [3, 63]);
+        assert_eq!(err.to_string(), "unknown bits in mask: 3, 63");
+    }
+}
@@ -1,12 +1,14 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::future::Future;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
 use std::task::{Context, Poll};
+use std::time::Duration;
 
-use anyhow::{Error, format_err};
+use anyhow::{Error, bail, format_err};
 use http::{HeaderMap, Method, Uri};
 use hyper::Response;
 use hyper::http::request::Parts;
@@ -17,20 +19,23 @@ use proxmox_daemon::command_socket::CommandSocket;
 use proxmox_http::Body;
 use proxmox_log::{FileLogOptions, FileLogger};
 use proxmox_network_types::Cidr;
-use proxmox_router::{Router, RpcEnvironmentType, UserInformation};
+use proxmox_router::{ApiMethod, Router, RpcEnvironmentType, UserInformation, http_bail, http_err};
 use proxmox_sys::fs::{CreateOptions, create_path};
 
 use crate::RestEnvironment;
-use crate::rest::Handler;
+use crate::rest::{Handler, HandlerRouter, extension_to_content_type};
 
 /// REST server configuration
 pub struct ApiConfig {
-    basedir: PathBuf,
+    basedirs: Vec<PathBuf>,
     aliases: HashMap<String, PathBuf>,
     env_type: RpcEnvironmentType,
     request_log: Option<Arc<Mutex<FileLogger>>>,
     auth_log: Option<Arc<Mutex<FileLogger>>>,
     handlers: Vec<Handler>,
+    reloadable_router: Option<Arc<RwLock<Router>>>,
+    maintenance: RwLock<Option<MaintenanceCheckFn>>,
+    rate_limiter: Option<RateLimiterFn>,
     auth_handler: Option<AuthHandler>,
     index_handler: Option<IndexHandler>,
     pub(crate) privileged_addr: Option<PrivilegedAddr>,
@@ -66,7 +71,8 @@ pub struct ApiConfig {
 impl ApiConfig {
     /// Creates a new instance
     ///
-    /// `basedir` - File lookups are relative to this directory.
+    /// `basedir` - File lookups are relative to this directory. More directories can be added via
+    /// [add_base_dir](Self::add_base_dir).
     ///
     /// `env_type` - The environment type.
     ///
@@ -82,12 +88,15 @@ impl ApiConfig {
             _ => Some("FORWARDED".to_string()),
         };
         Self {
-            basedir: basedir.into(),
+            basedirs: vec![basedir.into()],
             aliases: HashMap::new(),
             env_type,
             request_log: None,
             auth_log: None,
             handlers: Vec::new(),
+            reloadable_router: None,
+            maintenance: RwLock::new(None),
+            rate_limiter: None,
             auth_handler: None,
             index_handler: None,
             privileged_addr: None,
@@ -163,6 +172,46 @@ impl ApiConfig {
         }
     }
 
+    /// Set or clear the maintenance check.
+    ///
+    /// While set, every API request is passed through the check with its HTTP method before its
+    /// handler is called, and is rejected with `503 Service Unavailable` if the check fails. This
+    /// way handlers do not need to check for maintenance themselves, e.g. a check which only
+    /// allows `GET` requests makes the API read-only.
+    ///
+    /// This can be changed while the server is running.
+    pub fn set_maintenance(&self, check: Option<MaintenanceCheckFn>) {
+        *self.maintenance.write().unwrap() = check;
+    }
+
+    /// Check whether requests with the HTTP `method` are currently allowed, see
+    /// [`set_maintenance`](Self::set_maintenance).
+    pub fn check_maintenance(&self, method: &Method) -> Result<(), Error> {
+        match self.maintenance.read().unwrap().as_ref() {
+            Some(check) => check(method),
+            None => Ok(()),
+        }
+    }
+
+    /// Set a rate limiter for API requests.
+    ///
+    /// The limiter is called with the request path and a client key (the authenticated auth id,
+    /// or the peer's IP address for unauthenticated requests) after the permission check. Denied
+    /// requests are rejected with `429 Too Many Requests`.
+    pub fn rate_limiter(mut self, limiter: RateLimiterFn) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Consult the rate limiter for a request to `path` by `client`, see
+    /// [`rate_limiter`](Self::rate_limiter). Without a rate limiter everything is allowed.
+    pub fn check_rate_limit(&self, path: &str, client: &str) -> RateLimitDecision {
+        match self.rate_limiter.as_ref() {
+            Some(limiter) => limiter(path, client),
+            None => RateLimitDecision::Allow,
+        }
+    }
+
     pub(crate) async fn check_auth(
         &self,
         headers: &HeaderMap,
@@ -174,13 +223,49 @@ impl ApiConfig {
         }
     }
 
-    pub(crate) fn find_alias(&self, mut components: &[&str]) -> PathBuf {
-        let mut filename = self.basedir.clone();
+    /// Add another base directory for file lookups.
+    ///
+    /// Base directories are searched in the order they were added, starting with the one passed
+    /// to [new](Self::new), and the first existing file is used.
+    pub fn add_base_dir(&mut self, dir: PathBuf) {
+        self.basedirs.push(dir);
+    }
+
+    /// Resolve the file system path for a static file request.
+    ///
+    /// Components are percent-decoded before the alias lookup. Decoded components which could be
+    /// used to escape the base directory (like `..` or ones containing a `/`) are rejected.
+    pub(crate) fn find_alias(&self, components: &[&str]) -> Result<PathBuf, Error> {
+        let components = components
+            .iter()
+            .map(|component| decode_path_component(component))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let components: Vec<&str> = components.iter().map(|c| c.as_ref()).collect();
+
+        let mut fallback = None;
+        for basedir in &self.basedirs {
+            let filename = Self::resolve_alias(basedir, &self.aliases, &components);
+            if filename.exists() {
+                return Ok(filename);
+            }
+            fallback.get_or_insert(filename);
+        }
+
+        // there always is at least the base directory passed to `new`
+        Ok(fallback.unwrap())
+    }
+
+    fn resolve_alias(
+        basedir: &Path,
+        aliases: &HashMap<String, PathBuf>,
+        mut components: &[&str],
+    ) -> PathBuf {
+        let mut filename = basedir.to_path_buf();
         if components.is_empty() {
             return filename;
         }
 
-        if let Some(subdir) = self.aliases.get(components[0]) {
+        if let Some(subdir) = aliases.get(components[0]) {
             filename.push(subdir);
             components = &components[1..];
         }
@@ -190,6 +275,13 @@ impl ApiConfig {
         filename
     }
 
+    /// Get the content type used when serving the file at `path`, based on its extension.
+    ///
+    /// Unknown extensions result in `application/octet-stream`.
+    pub fn content_type_for(path: &Path) -> &'static str {
+        extension_to_content_type(path).0
+    }
+
     /// Register a path alias
     ///
     /// This can be used to redirect file lookups to a specific
@@ -223,6 +315,19 @@ impl ApiConfig {
         self
     }
 
+    /// Check whether `component` is a registered path alias.
+    ///
+    /// This allows deciding whether a request is going to be served from the file system before
+    /// doing the actual file lookup.
+    pub fn is_alias(&self, component: &str) -> bool {
+        self.aliases.contains_key(component)
+    }
+
+    /// Get the path a registered alias points to, if `component` is one.
+    pub fn alias_target(&self, component: &str) -> Option<&Path> {
+        self.aliases.get(component).map(PathBuf::as_path)
+    }
+
     pub(crate) fn env_type(&self) -> RpcEnvironmentType {
         self.env_type
     }
@@ -342,6 +447,33 @@ impl ApiConfig {
             .find(|handler| path_components.strip_prefix(handler.prefix).is_some())
     }
 
+    /// Lookup the API method a request to `path_components` would be dispatched to.
+    ///
+    /// For routers registered with a format prefix (like the default `api2` handler), the
+    /// component following the prefix is the output format, e.g. `["api2", "json", "version"]`.
+    pub fn find_method(
+        &self,
+        path_components: &[&str],
+        method: Method,
+        uri_param: &mut HashMap<String, String>,
+    ) -> Option<&'static ApiMethod> {
+        let handler = self.find_handler(path_components)?;
+        handler.find_method(&path_components[handler.prefix.len()..], method, uri_param)
+    }
+
+    /// List all registered API routes together with the HTTP methods available on them.
+    ///
+    /// Paths are fully qualified, including the handler prefix. Formatted routers (like the
+    /// default `api2` handler) use a `{format}` placeholder for the output format component, and
+    /// path parameters are shown as `{param_name}`.
+    pub fn list_routes(&self) -> Vec<(String, Vec<Method>)> {
+        let mut routes = Vec::new();
+        for handler in &self.handlers {
+            handler.list_routes(&mut routes);
+        }
+        routes
+    }
+
     pub fn default_api2_handler(mut self, router: &'static Router) -> Self {
         self.handlers.push(Handler::default_api2_handler(router));
         self
@@ -366,6 +498,35 @@ impl ApiConfig {
             .push(Handler::unformatted_router(prefix, router));
         self
     }
+
+    /// Use a router which can be replaced at runtime for the default `api2` handler.
+    ///
+    /// In contrast to [`default_api2_handler`](Self::default_api2_handler), the router does not
+    /// need to be `'static`, and can later be swapped out via
+    /// [`reload_router`](Self::reload_router), e.g. when plugins providing additional API
+    /// endpoints get (re)loaded. Requests which are already being processed are not affected.
+    pub fn reloadable_api2_handler(mut self, router: Router) -> Self {
+        let router = Arc::new(RwLock::new(router));
+        self.reloadable_router = Some(Arc::clone(&router));
+        self.handlers
+            .push(Handler::default_api2_handler(HandlerRouter::Reloadable(
+                router,
+            )));
+        self
+    }
+
+    /// Replace the router registered via [`reloadable_api2_handler`](Self::reloadable_api2_handler).
+    ///
+    /// Fails if no reloadable router was configured.
+    pub fn reload_router(&self, router: Router) -> Result<(), Error> {
+        match self.reloadable_router.as_ref() {
+            Some(current) => {
+                *current.write().unwrap() = router;
+                Ok(())
+            }
+            None => bail!("no reloadable router configured"),
+        }
+    }
 }
 
 #[cfg(feature = "templates")]
@@ -475,6 +636,25 @@ mod templates {
     }
 }
 
+/// Callback deciding whether a request with the given HTTP method is permitted during
+/// maintenance, see [`ApiConfig::set_maintenance`].
+pub type MaintenanceCheckFn = Arc<dyn Fn(&Method) -> Result<(), Error> + Send + Sync>;
+
+/// Result of a [`RateLimiterFn`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RateLimitDecision {
+    /// The request may proceed.
+    Allow,
+    /// The request is rejected.
+    Deny,
+    /// The request is rejected, the client should retry after the given time.
+    RetryAfter(Duration),
+}
+
+/// Callback deciding whether a request to a path (first argument) by a client (second argument)
+/// is allowed, see [`ApiConfig::rate_limiter`].
+pub type RateLimiterFn = Arc<dyn Fn(&str, &str) -> RateLimitDecision + Send + Sync>;
+
 pub type IndexFuture = Pin<Box<dyn Future<Output = Response<Body>> + Send>>;
 pub type IndexFunc = Box<dyn Fn(RestEnvironment, Parts) -> IndexFuture + Send + Sync>;
 
@@ -670,3 +850,271 @@ impl hyper_util::client::legacy::connect::Connection for PrivilegedSocket {
         }
     }
 }
+
+fn decode_path_component(component: &str) -> Result<Cow<'_, str>, Error> {
+    let decoded = percent_encoding::percent_decode_str(component)
+        .decode_utf8()
+        .map_err(|_| http_err!(BAD_REQUEST, "path contains invalid utf-8"))?;
+
+    if decoded.starts_with('.') || decoded.contains(['/', '\0']) {
+        http_bail!(BAD_REQUEST, "path contains illegal components");
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use anyhow::bail;
+    use http::Method;
+    use serde_json::Value;
+
+    use proxmox_router::{ApiHandler, ApiMethod, Router, RpcEnvironmentType, SubdirMap};
+    use proxmox_schema::ObjectSchema;
+
+    use super::ApiConfig;
+
+    const API_METHOD_DUMMY: ApiMethod = ApiMethod::new(
+        &ApiHandler::Sync(&|_, _, _| Ok(Value::Null)),
+        &ObjectSchema::new("Dummy method.", &[]),
+    );
+
+    const PLUGIN_ROUTER: Router = Router::new().get(&API_METHOD_DUMMY);
+    const SUBDIRS: SubdirMap = &[("plugin", &PLUGIN_ROUTER)];
+
+    #[test]
+    fn reload_router() {
+        let config = ApiConfig::new("/var/empty", RpcEnvironmentType::PUBLIC)
+            .reloadable_api2_handler(Router::new());
+
+        let path = ["api2", "json", "plugin"];
+        let mut uri_param = HashMap::new();
+        assert!(
+            config
+                .find_method(&path, Method::GET, &mut uri_param)
+                .is_none()
+        );
+
+        config
+            .reload_router(Router::new().subdirs(SUBDIRS))
+            .expect("failed to reload router");
+
+        let method = config
+            .find_method(&path, Method::GET, &mut uri_param)
+            .expect("method not found after reloading the router");
+        assert!(std::ptr::eq(method, &API_METHOD_DUMMY));
+        assert!(
+            config
+                .find_method(&path, Method::POST, &mut uri_param)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn list_routes() {
+        const ITEM_ROUTER: Router = Router::new()
+            .get(&API_METHOD_DUMMY)
+            .delete(&API_METHOD_DUMMY);
+        const ITEMS_ROUTER: Router = Router::new()
+            .get(&API_METHOD_DUMMY)
+            .post(&API_METHOD_DUMMY)
+            .match_all("id", &ITEM_ROUTER);
+        const API_SUBDIRS: SubdirMap = &[("items", &ITEMS_ROUTER), ("plugin", &PLUGIN_ROUTER)];
+        const API_ROUTER: Router = Router::new().subdirs(API_SUBDIRS);
+
+        let config = ApiConfig::new("/var/empty", RpcEnvironmentType::PUBLIC)
+            .default_api2_handler(&API_ROUTER)
+            .unformatted_router(&["raw"], &PLUGIN_ROUTER);
+
+        assert_eq!(
+            config.list_routes(),
+            vec![
+                (
+                    "/api2/{format}/items".to_string(),
+                    vec![Method::GET, Method::POST]
+                ),
+                (
+                    "/api2/{format}/items/{id}".to_string(),
+                    vec![Method::GET, Method::DELETE]
+                ),
+                ("/api2/{format}/plugin".to_string(), vec![Method::GET]),
+                ("/raw".to_string(), vec![Method::GET]),
+            ]
+        );
+    }
+
+    #[test]
+    fn reload_without_reloadable_router() {
+        let config = ApiConfig::new("/var/empty", RpcEnvironmentType::PUBLIC);
+        assert!(config.reload_router(Router::new()).is_err());
+    }
+
+    #[test]
+    fn content_type_for() {
+        for (path, content_type) in [
+            ("/usr/share/javascript/index.html", "text/html"),
+            ("app.js", "application/javascript"),
+            ("css/style.css", "text/css"),
+            ("images/logo.png", "image/png"),
+            ("images/logo.svg", "image/svg+xml"),
+            ("data.json", "application/json"),
+            ("module.wasm", "application/wasm"),
+            ("archive.unknown", "application/octet-stream"),
+            ("no-extension", "application/octet-stream"),
+        ] {
+            assert_eq!(ApiConfig::content_type_for(Path::new(path)), content_type);
+        }
+    }
+
+    #[test]
+    fn maintenance_check() {
+        let config = ApiConfig::new("/var/empty", RpcEnvironmentType::PUBLIC);
+
+        // no maintenance set
+        assert!(config.check_maintenance(&Method::GET).is_ok());
+        assert!(config.check_maintenance(&Method::POST).is_ok());
+
+        config.set_maintenance(Some(Arc::new(|method: &Method| {
+            if method == Method::GET {
+                Ok(())
+            } else {
+                bail!("read-only maintenance mode");
+            }
+        })));
+        assert!(config.check_maintenance(&Method::GET).is_ok());
+        let err = config.check_maintenance(&Method::PUT).unwrap_err();
+        assert_eq!(err.to_string(), "read-only maintenance mode");
+
+        config.set_maintenance(Some(Arc::new(|_: &Method| {
+            bail!("offline maintenance mode")
+        })));
+        assert!(config.check_maintenance(&Method::GET).is_err());
+        assert!(config.check_maintenance(&Method::POST).is_err());
+
+        config.set_maintenance(None);
+        assert!(config.check_maintenance(&Method::POST).is_ok());
+    }
+
+    #[test]
+    fn rate_limiter() {
+        use super::RateLimitDecision;
+
+        let config = ApiConfig::new("/var/empty", RpcEnvironmentType::PUBLIC);
+        assert_eq!(
+            config.check_rate_limit("/api2/json/expensive", "root@pam"),
+            RateLimitDecision::Allow,
+        );
+
+        let config =
+            config.rate_limiter(Arc::new(|path: &str, client: &str| match (path, client) {
+                ("/api2/json/expensive", "192.0.2.1") => {
+                    RateLimitDecision::RetryAfter(std::time::Duration::from_secs(30))
+                }
+                ("/api2/json/expensive", _) => RateLimitDecision::Deny,
+                _ => RateLimitDecision::Allow,
+            }));
+        assert_eq!(
+            config.check_rate_limit("/api2/json/expensive", "root@pam"),
+            RateLimitDecision::Deny,
+        );
+        assert_eq!(
+            config.check_rate_limit("/api2/json/expensive", "192.0.2.1"),
+            RateLimitDecision::RetryAfter(std::time::Duration::from_secs(30)),
+        );
+        assert_eq!(
+            config.check_rate_limit("/api2/json/version", "root@pam"),
+            RateLimitDecision::Allow,
+        );
+    }
+
+    #[test]
+    fn alias_lookup() {
+        let config = ApiConfig::new("/var/empty", RpcEnvironmentType::PUBLIC)
+            .alias("extjs", "/usr/share/javascript/extjs")
+            .aliases([("fontawesome", "/usr/share/fonts-font-awesome")]);
+
+        assert!(config.is_alias("extjs"));
+        assert!(config.is_alias("fontawesome"));
+        assert_eq!(
+            config.alias_target("extjs"),
+            Some(Path::new("/usr/share/javascript/extjs"))
+        );
+
+        assert!(!config.is_alias("api2"));
+        assert!(!config.is_alias("extjs/ext-all.js"));
+        assert_eq!(config.alias_target("api2"), None);
+    }
+
+    #[test]
+    fn find_alias_in_base_dirs() {
+        let base =
+            std::env::temp_dir().join(format!("rest-server-base-dirs-{}", std::process::id()));
+        let first = base.join("first");
+        let second = base.join("second");
+        std::fs::create_dir_all(first.join("css")).unwrap();
+        std::fs::create_dir_all(second.join("css")).unwrap();
+        std::fs::create_dir_all(second.join("theme")).unwrap();
+        std::fs::write(first.join("css/style.css"), "first").unwrap();
+        std::fs::write(second.join("css/style.css"), "second").unwrap();
+        std::fs::write(second.join("css/custom.css"), "custom").unwrap();
+        std::fs::write(second.join("theme/dark.css"), "dark").unwrap();
+
+        let mut config =
+            ApiConfig::new(&first, RpcEnvironmentType::PUBLIC).alias("themes", "theme");
+        config.add_base_dir(second.clone());
+
+        // the first existing file wins
+        assert_eq!(
+            config.find_alias(&["css", "style.css"]).unwrap(),
+            first.join("css/style.css")
+        );
+        // files only present in the second base dir are found there
+        assert_eq!(
+            config.find_alias(&["css", "custom.css"]).unwrap(),
+            second.join("css/custom.css")
+        );
+        assert_eq!(
+            config.find_alias(&["themes", "dark.css"]).unwrap(),
+            second.join("theme/dark.css")
+        );
+        // missing files resolve relative to the first base dir
+        assert_eq!(
+            config.find_alias(&["css", "missing.css"]).unwrap(),
+            first.join("css/missing.css")
+        );
+        assert_eq!(config.find_alias(&[]).unwrap(), first);
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn find_alias_percent_decoding() {
+        let base = std::env::temp_dir().join(format!("rest-server-percent-{}", std::process::id()));
+        std::fs::create_dir_all(base.join("docs")).unwrap();
+        std::fs::write(base.join("docs/my file.txt"), "content").unwrap();
+
+        let config = ApiConfig::new(&base, RpcEnvironmentType::PUBLIC).alias("manual", "docs");
+
+        assert_eq!(
+            config.find_alias(&["manual", "my%20file.txt"]).unwrap(),
+            base.join("docs/my file.txt")
+        );
+
+        // decoded components must not allow escaping the base directory
+        assert!(config.find_alias(&["manual", "%2e%2e", "secret"]).is_err());
+        assert!(config.find_alias(&["%2E%2E", "etc", "passwd"]).is_err());
+        assert!(config.find_alias(&["manual", "..%2fsecret"]).is_err());
+        assert!(
+            config
+                .find_alias(&["manual", "a%2f..%2f..%2fsecret"])
+                .is_err()
+        );
+        assert!(config.find_alias(&["manual", "file%00.txt"]).is_err());
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+}
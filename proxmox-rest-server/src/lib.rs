@@ -34,7 +34,10 @@ mod environment;
 pub use environment::*;
 
 mod api_config;
-pub use api_config::{ApiConfig, AuthError, AuthHandler, IndexHandler};
+pub use api_config::{
+    ApiConfig, AuthError, AuthHandler, IndexHandler, MaintenanceCheckFn, RateLimitDecision,
+    RateLimiterFn,
+};
 
 mod rest;
 pub use rest::{Redirector, RestServer};
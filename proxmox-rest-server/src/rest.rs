@@ -4,7 +4,7 @@ use std::hash::BuildHasher;
 use std::io;
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
-use std::sync::{Arc, LazyLock, Mutex};
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
 use std::task::{Context, Poll};
 
 use anyhow::{Error, bail, format_err};
@@ -45,7 +45,8 @@ use proxmox_compression::DeflateEncoder;
 use proxmox_log::FileLogger;
 
 use crate::{
-    ApiConfig, AuthError, CompressionMethod, RestEnvironment, formatter::*, normalize_path,
+    ApiConfig, AuthError, CompressionMethod, RateLimitDecision, RestEnvironment, formatter::*,
+    normalize_path,
 };
 
 unsafe extern "C" {
@@ -645,6 +646,41 @@ fn access_forbidden_time() -> std::time::Instant {
     std::time::Instant::now() + std::time::Duration::from_millis(500)
 }
 
+/// Consult the configured rate limiter and build the error response if the request is denied.
+///
+/// Authenticated clients are identified by their auth id, others by their IP address.
+fn check_rate_limit(
+    config: &ApiConfig,
+    path: &str,
+    auth_id: Option<&str>,
+    peer: &std::net::SocketAddr,
+    formatter: Option<&dyn OutputFormatter>,
+) -> Option<Response<Body>> {
+    let client = match auth_id {
+        Some(auth_id) => auth_id.to_string(),
+        None => peer.ip().to_string(),
+    };
+
+    let retry_after = match config.check_rate_limit(path, &client) {
+        RateLimitDecision::Allow => return None,
+        RateLimitDecision::Deny => None,
+        RateLimitDecision::RetryAfter(duration) => Some(duration),
+    };
+
+    let err = http_err!(TOO_MANY_REQUESTS, "too many requests");
+    let mut response = match formatter {
+        Some(formatter) => formatter.format_error(err),
+        None => error_to_response(err),
+    };
+    if let Some(retry_after) = retry_after {
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, retry_after.as_secs().max(1).into());
+    }
+
+    Some(response)
+}
+
 fn handle_stream_as_json_seq(stream: proxmox_router::Stream) -> Result<Response<Body>, Error> {
     let (send, body) = tokio::sync::mpsc::channel::<Result<Vec<u8>, Error>>(1);
     tokio::spawn(async move {
@@ -808,7 +844,7 @@ pub(crate) async fn handle_api_request<Env: RpcEnvironment, S: 'static + BuildHa
     Ok(resp)
 }
 
-fn extension_to_content_type(filename: &Path) -> (&'static str, bool) {
+pub(crate) fn extension_to_content_type(filename: &Path) -> (&'static str, bool) {
     if let Some(ext) = filename.extension().and_then(|osstr| osstr.to_str()) {
         return match ext {
             "css" => ("text/css", false),
@@ -1024,7 +1060,7 @@ impl ApiConfig {
             }
             Ok(self.get_index(rpcenv, parts).await)
         } else {
-            let filename = self.find_alias(&components);
+            let filename = self.find_alias(&components)?;
             let compression = extract_compression_method(&parts.headers);
             handle_static_file_download(&components, filename, compression).await
         }
@@ -1041,31 +1077,139 @@ impl Handler {
         self.action.handle_request(data).await
     }
 
-    pub(crate) fn default_api2_handler(router: &'static proxmox_router::Router) -> Self {
+    pub(crate) fn find_method(
+        &self,
+        relative_path_components: &[&str],
+        method: http::Method,
+        uri_param: &mut HashMap<String, String>,
+    ) -> Option<&'static ApiMethod> {
+        self.action
+            .find_method(relative_path_components, method, uri_param)
+    }
+
+    /// Append all routes of this handler with their registered HTTP methods to `routes`.
+    pub(crate) fn list_routes(&self, routes: &mut Vec<(String, Vec<http::Method>)>) {
+        let mut path = String::new();
+        for component in self.prefix {
+            path.push('/');
+            path.push_str(component);
+        }
+
+        let router = match &self.action {
+            Action::Formatted(a) => {
+                path.push_str("/{format}");
+                &a.router
+            }
+            Action::Unformatted(a) => &a.router,
+        };
+
+        match router {
+            HandlerRouter::Static(router) => collect_routes(router, path, routes),
+            HandlerRouter::Reloadable(router) => {
+                collect_routes(&router.read().unwrap(), path, routes)
+            }
+        }
+    }
+
+    pub(crate) fn default_api2_handler(router: impl Into<HandlerRouter>) -> Self {
         Self::formatted_router(&["api2"], router)
     }
 
     pub(crate) fn formatted_router(
         prefix: &'static [&'static str],
-        router: &'static proxmox_router::Router,
+        router: impl Into<HandlerRouter>,
     ) -> Self {
         Self {
             prefix,
-            action: Action::Formatted(Formatted { router }),
+            action: Action::Formatted(Formatted {
+                router: router.into(),
+            }),
         }
     }
 
     pub(crate) fn unformatted_router(
         prefix: &'static [&'static str],
-        router: &'static proxmox_router::Router,
+        router: impl Into<HandlerRouter>,
     ) -> Self {
         Self {
             prefix,
-            action: Action::Unformatted(Unformatted { router }),
+            action: Action::Unformatted(Unformatted {
+                router: router.into(),
+            }),
+        }
+    }
+}
+
+fn collect_routes(
+    router: &proxmox_router::Router,
+    path: String,
+    routes: &mut Vec<(String, Vec<http::Method>)>,
+) {
+    let methods: Vec<http::Method> = [
+        (http::Method::GET, router.get),
+        (http::Method::PUT, router.put),
+        (http::Method::POST, router.post),
+        (http::Method::DELETE, router.delete),
+    ]
+    .into_iter()
+    .filter_map(|(method, info)| info.map(|_| method))
+    .collect();
+
+    if !methods.is_empty() {
+        routes.push((path.clone(), methods));
+    }
+
+    match router.subroute {
+        None => (),
+        Some(proxmox_router::SubRoute::Map(dirmap)) => {
+            for (name, router) in dirmap.iter() {
+                collect_routes(router, format!("{path}/{name}"), routes);
+            }
+        }
+        Some(proxmox_router::SubRoute::MatchAll { router, param_name }) => {
+            collect_routes(router, format!("{path}/{{{param_name}}}"), routes);
         }
     }
 }
 
+/// The router a [`Handler`] dispatches to.
+///
+/// This is either a `'static` router defined at compile time, or a router which can be replaced
+/// while the server is running.
+pub(crate) enum HandlerRouter {
+    Static(&'static proxmox_router::Router),
+    Reloadable(Arc<RwLock<proxmox_router::Router>>),
+}
+
+impl HandlerRouter {
+    fn find_method(
+        &self,
+        components: &[&str],
+        method: http::Method,
+        uri_param: &mut HashMap<String, String>,
+    ) -> Option<&'static ApiMethod> {
+        match self {
+            HandlerRouter::Static(router) => router.find_method(components, method, uri_param),
+            HandlerRouter::Reloadable(router) => router
+                .read()
+                .unwrap()
+                .find_method(components, method, uri_param),
+        }
+    }
+}
+
+impl From<&'static proxmox_router::Router> for HandlerRouter {
+    fn from(router: &'static proxmox_router::Router) -> Self {
+        HandlerRouter::Static(router)
+    }
+}
+
+impl From<Arc<RwLock<proxmox_router::Router>>> for HandlerRouter {
+    fn from(router: Arc<RwLock<proxmox_router::Router>>) -> Self {
+        HandlerRouter::Reloadable(router)
+    }
+}
+
 pub(crate) enum Action {
     Formatted(Formatted),
     Unformatted(Unformatted),
@@ -1078,6 +1222,25 @@ impl Action {
             Action::Unformatted(a) => a.handle_request(data).await,
         }
     }
+
+    fn find_method(
+        &self,
+        relative_path_components: &[&str],
+        method: http::Method,
+        uri_param: &mut HashMap<String, String>,
+    ) -> Option<&'static ApiMethod> {
+        match self {
+            // the first component selects the output format
+            Action::Formatted(a) => {
+                a.router
+                    .find_method(relative_path_components.get(1..)?, method, uri_param)
+            }
+            Action::Unformatted(a) => {
+                a.router
+                    .find_method(relative_path_components, method, uri_param)
+            }
+        }
+    }
 }
 
 pub struct ApiRequestData<'a> {
@@ -1093,7 +1256,7 @@ pub struct ApiRequestData<'a> {
 }
 
 pub(crate) struct Formatted {
-    router: &'static proxmox_router::Router,
+    router: HandlerRouter,
 }
 
 impl Formatted {
@@ -1196,6 +1359,16 @@ impl Formatted {
                     return Ok(formatter.format_error(err));
                 }
 
+                if let Err(err) = config.check_maintenance(&parts.method) {
+                    return Ok(formatter.format_error(http_err!(SERVICE_UNAVAILABLE, "{err}")));
+                }
+
+                if let Some(response) =
+                    check_rate_limit(config, full_path, auth_id.as_deref(), peer, Some(formatter))
+                {
+                    return Ok(response);
+                }
+
                 let result = if api_method.protected
                     && rpcenv.env_type == RpcEnvironmentType::PUBLIC
                 {
@@ -1223,7 +1396,7 @@ impl Formatted {
 }
 
 pub(crate) struct Unformatted {
-    router: &'static proxmox_router::Router,
+    router: HandlerRouter,
 }
 
 impl Unformatted {
@@ -1315,6 +1488,16 @@ impl Unformatted {
                     return Err(err);
                 }
 
+                if let Err(err) = config.check_maintenance(&parts.method) {
+                    return Err(http_err!(SERVICE_UNAVAILABLE, "{err}"));
+                }
+
+                if let Some(response) =
+                    check_rate_limit(config, full_path, auth_id.as_deref(), peer, None)
+                {
+                    return Ok(response);
+                }
+
                 let result =
                     if api_method.protected && rpcenv.env_type == RpcEnvironmentType::PUBLIC {
                         proxy_protected_request(config, api_method, parts, body, peer).await
@@ -563,6 +563,30 @@ impl PartialEq for ApiHandler {
 /// This is a workaround unless RUST can const_fn `Hash::new()`
 pub type SubdirMap = &'static [(&'static str, &'static Router)];
 
+/// Check that the entries of a [`SubdirMap`] are sorted by name and unique.
+const fn assert_subdirs_sorted(map: SubdirMap) {
+    let mut i = 1;
+    while i < map.len() {
+        let (prev, cur) = (map[i - 1].0.as_bytes(), map[i].0.as_bytes());
+
+        // const-version of `prev < cur`
+        let mut j = 0;
+        while j < prev.len() && j < cur.len() && prev[j] == cur[j] {
+            j += 1;
+        }
+        let less = if j < prev.len() && j < cur.len() {
+            prev[j] < cur[j]
+        } else {
+            prev.len() < cur.len()
+        };
+
+        if !less {
+            panic!("subdir map entries must be sorted by name and unique");
+        }
+        i += 1;
+    }
+}
+
 /// Classify different types of routers
 pub enum SubRoute {
     //Hash(HashMap<String, Router>),
@@ -613,6 +637,9 @@ macro_rules! list_subdirs_api_method {
 /// Routers are meant to be build a compile time, and you can use
 /// all `const fn(mut self, ..)` methods to configure them.
 ///
+/// Note that the entries of a [`SubdirMap`] need to be sorted by name, since
+/// they are looked up via binary search.
+///
 ///```
 /// # use serde_json::{json, Value};
 /// use proxmox_router::{ApiHandler, ApiMethod, Router};
@@ -627,6 +654,24 @@ macro_rules! list_subdirs_api_method {
 /// const ROUTER: Router = Router::new()
 ///    .get(&API_METHOD_HELLO);
 ///```
+///
+/// Routers which are only known at runtime can add their subdirectories one by one via
+/// [`subdir`](Router::subdir), which keeps the map sorted:
+///
+///```
+/// # use serde_json::{json, Value};
+/// # use proxmox_router::{ApiHandler, ApiMethod, Router};
+/// # use proxmox_schema::ObjectSchema;
+/// # const API_METHOD_HELLO: ApiMethod = ApiMethod::new(
+/// #    &ApiHandler::Sync(&|_, _, _| Ok(json!("Hello world!"))),
+/// #    &ObjectSchema::new("Hello World Example", &[])
+/// # );
+/// static HELLO_ROUTER: Router = Router::new().get(&API_METHOD_HELLO);
+///
+/// let router = Router::new()
+///    .subdir("world", &HELLO_ROUTER)
+///    .subdir("hello", &HELLO_ROUTER);
+///```
 pub struct Router {
     /// GET requests
     pub get: Option<&'static ApiMethod>,
@@ -653,11 +698,44 @@ impl Router {
     }
 
     /// Configure a static map as `subroute`.
+    ///
+    /// With debug assertions enabled this panics if the map is not sorted by name, which for a
+    /// `const` router already fails at compile time.
     pub const fn subdirs(mut self, map: SubdirMap) -> Self {
+        if cfg!(debug_assertions) {
+            assert_subdirs_sorted(map);
+        }
         self.subroute = Some(SubRoute::Map(map));
         self
     }
 
+    /// Add a single entry to the `SubRoute::Map`, keeping it sorted by name.
+    ///
+    /// This is meant for routers built at runtime, `const` routers should use
+    /// [`subdirs`](Self::subdirs). Since the map has to be `'static`, every call leaks a copy of
+    /// it, so only use this for routers which live until the program exits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` already exists or the router already has a `MatchAll` subroute.
+    pub fn subdir(mut self, name: &'static str, router: &'static Router) -> Self {
+        let mut map = match self.subroute {
+            None => Vec::new(),
+            Some(SubRoute::Map(map)) => map.to_vec(),
+            Some(SubRoute::MatchAll { .. }) => {
+                panic!("cannot add subdir '{name}' to a router with a match-all subroute")
+            }
+        };
+
+        match map.binary_search_by_key(&name, |(name, _)| name) {
+            Ok(_) => panic!("duplicate subdir '{name}'"),
+            Err(pos) => map.insert(pos, (name, router)),
+        }
+
+        self.subroute = Some(SubRoute::Map(map.leak()));
+        self
+    }
+
     /// Configure a `SubRoute::MatchAll` as `subroute`.
     pub const fn match_all(mut self, param_name: &'static str, router: &'static Router) -> Self {
         self.subroute = Some(SubRoute::MatchAll { router, param_name });
@@ -758,7 +836,7 @@ impl Router {
         components: &[&str],
         method: Method,
         uri_param: &mut HashMap<String, String>,
-    ) -> Option<&ApiMethod> {
+    ) -> Option<&'static ApiMethod> {
         if let Some(info) = self.find_route(components, uri_param) {
             return match method {
                 Method::GET => info.get,
@@ -807,6 +885,10 @@ pub struct ApiMethod {
     pub protected: bool,
     /// Whether this method is still experimental or already stable.
     pub unstable: bool,
+    /// Whether this method is deprecated and should no longer be used.
+    pub deprecated: bool,
+    /// An optional note on the deprecation, e.g. pointing to a replacement.
+    pub deprecated_message: Option<&'static str>,
     /// This flag indicates that the provided method may change the local timezone, so the server
     /// should do a tzset afterwards
     pub reload_timezone: bool,
@@ -814,6 +896,9 @@ pub struct ApiMethod {
     pub parameters: ParameterSchema,
     /// Return type Schema
     pub returns: ReturnType,
+    /// Whether the return type was declared via [`returns`](Self::returns). Otherwise `returns`
+    /// is the default null schema, which does not document anything.
+    pub returns_declared: bool,
     /// Handler function
     pub handler: &'static ApiHandler,
     /// Access Permissions
@@ -837,8 +922,11 @@ impl ApiMethod {
             parameters,
             handler,
             returns: ReturnType::new(false, &NULL_SCHEMA),
+            returns_declared: false,
             protected: false,
             unstable: false,
+            deprecated: false,
+            deprecated_message: None,
             reload_timezone: false,
             access: ApiAccess {
                 description: None,
@@ -856,8 +944,11 @@ impl ApiMethod {
             parameters: ParameterSchema::Object(parameters),
             handler: &DUMMY_HANDLER,
             returns: ReturnType::new(false, &NULL_SCHEMA),
+            returns_declared: false,
             protected: false,
             unstable: false,
+            deprecated: false,
+            deprecated_message: None,
             reload_timezone: false,
             access: ApiAccess {
                 description: None,
@@ -868,6 +959,7 @@ impl ApiMethod {
 
     pub const fn returns(mut self, returns: ReturnType) -> Self {
         self.returns = returns;
+        self.returns_declared = true;
 
         self
     }
@@ -884,6 +976,18 @@ impl ApiMethod {
         self
     }
 
+    pub const fn deprecated(mut self, deprecated: bool) -> Self {
+        self.deprecated = deprecated;
+
+        self
+    }
+
+    pub const fn deprecated_message(mut self, message: Option<&'static str>) -> Self {
+        self.deprecated_message = message;
+
+        self
+    }
+
     pub const fn reload_timezone(mut self, reload_timezone: bool) -> Self {
         self.reload_timezone = reload_timezone;
 
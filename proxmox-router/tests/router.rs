@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use http::Method;
+use serde_json::Value;
+
+use proxmox_router::{ApiHandler, ApiMethod, Router, SubdirMap, list_subdirs_api_method};
+use proxmox_schema::ObjectSchema;
+
+const API_METHOD_LIST_NODES: ApiMethod = ApiMethod::new(
+    &ApiHandler::Sync(&|_, _, _| Ok(Value::Null)),
+    &ObjectSchema::new("List nodes.", &[]),
+);
+
+const API_METHOD_CREATE_NODE: ApiMethod = ApiMethod::new(
+    &ApiHandler::Sync(&|_, _, _| Ok(Value::Null)),
+    &ObjectSchema::new("Create a node.", &[]),
+);
+
+const API_METHOD_NODE_STATUS: ApiMethod = ApiMethod::new(
+    &ApiHandler::Sync(&|_, _, _| Ok(Value::Null)),
+    &ObjectSchema::new("Node status.", &[]),
+);
+
+const API_METHOD_VERSION: ApiMethod = ApiMethod::new(
+    &ApiHandler::Sync(&|_, _, _| Ok(Value::Null)),
+    &ObjectSchema::new("Version.", &[]),
+);
+
+const NODE_SUBDIRS: SubdirMap = &[("status", &Router::new().get(&API_METHOD_NODE_STATUS))];
+
+const NODE_ROUTER: Router = Router::new()
+    .get(&list_subdirs_api_method!(NODE_SUBDIRS))
+    .subdirs(NODE_SUBDIRS);
+
+const NODES_ROUTER: Router = Router::new()
+    .get(&API_METHOD_LIST_NODES)
+    .post(&API_METHOD_CREATE_NODE)
+    .match_all("node", &NODE_ROUTER);
+
+// must be sorted by name
+const API_SUBDIRS: SubdirMap = &[
+    ("nodes", &NODES_ROUTER),
+    ("version", &Router::new().get(&API_METHOD_VERSION)),
+];
+
+const API_ROUTER: Router = Router::new().subdirs(API_SUBDIRS);
+
+fn find(
+    components: &[&str],
+    method: Method,
+) -> Option<(&'static ApiMethod, HashMap<String, String>)> {
+    let mut uri_param = HashMap::new();
+    API_ROUTER
+        .find_method(components, method, &mut uri_param)
+        .map(|info| (info, uri_param))
+}
+
+#[test]
+fn find_methods() {
+    let (info, params) = find(&["version"], Method::GET).unwrap();
+    assert!(std::ptr::eq(info, &API_METHOD_VERSION));
+    assert!(params.is_empty());
+
+    let (info, _) = find(&["nodes"], Method::GET).unwrap();
+    assert!(std::ptr::eq(info, &API_METHOD_LIST_NODES));
+    let (info, _) = find(&["nodes"], Method::POST).unwrap();
+    assert!(std::ptr::eq(info, &API_METHOD_CREATE_NODE));
+
+    assert!(find(&["version"], Method::POST).is_none());
+    assert!(find(&["unknown"], Method::GET).is_none());
+    assert!(find(&["version", "unknown"], Method::GET).is_none());
+}
+
+#[test]
+fn find_match_all_params() {
+    let (info, params) = find(&["nodes", "node1", "status"], Method::GET).unwrap();
+    assert!(std::ptr::eq(info, &API_METHOD_NODE_STATUS));
+    assert_eq!(params.get("node").map(String::as_str), Some("node1"));
+
+    // path components are percent-decoded
+    let (_, params) = find(&["nodes", "node%201"], Method::GET).unwrap();
+    assert_eq!(params.get("node").map(String::as_str), Some("node 1"));
+
+    assert!(find(&["nodes", "node1", "unknown"], Method::GET).is_none());
+}
+
+static RUNTIME_NODES_ROUTER: Router = Router::new()
+    .get(&API_METHOD_LIST_NODES)
+    .match_all("node", &NODE_ROUTER);
+
+static VERSION_ROUTER: Router = Router::new().get(&API_METHOD_VERSION);
+
+#[test]
+fn runtime_subdirs() {
+    // added out of order, the map is kept sorted for the lookup
+    let router = Router::new()
+        .subdir("version", &VERSION_ROUTER)
+        .subdir("nodes", &RUNTIME_NODES_ROUTER);
+
+    let mut params = HashMap::new();
+    let info = router
+        .find_method(&["version"], Method::GET, &mut params)
+        .unwrap();
+    assert!(std::ptr::eq(info, &API_METHOD_VERSION));
+
+    let info = router
+        .find_method(&["nodes", "node1", "status"], Method::GET, &mut params)
+        .unwrap();
+    assert!(std::ptr::eq(info, &API_METHOD_NODE_STATUS));
+    assert_eq!(params.get("node").map(String::as_str), Some("node1"));
+
+    assert!(
+        router
+            .find_method(&["unknown"], Method::GET, &mut params)
+            .is_none()
+    );
+}
+
+#[test]
+#[should_panic(expected = "duplicate subdir 'version'")]
+fn runtime_subdirs_duplicate() {
+    let _ = Router::new()
+        .subdir("version", &VERSION_ROUTER)
+        .subdir("version", &VERSION_ROUTER);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "must be sorted")]
+fn unsorted_subdirs() {
+    let map: SubdirMap = vec![("version", &VERSION_ROUTER), ("nodes", &VERSION_ROUTER)].leak();
+    let _ = Router::new().subdirs(map);
+}
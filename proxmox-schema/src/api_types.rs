@@ -127,6 +127,13 @@ const_regex! {
     /// A ED25519 key has always 32 bytes of raw key material, base64 needs 4 * (n / 3) characters
     /// to represent n bytes -> 4 * (32 / 3) = 42.6.., thus 43 + 1 padding character.
     pub ED25519_BASE64_KEY_REGEX =r"^[a-zA-Z0-9+/-]{43}=";
+
+    /// Regex to match property string values which can be written without quoting (no commas,
+    /// equal signs, quotes, backslashes or control characters).
+    pub PROPERTY_STRING_VALUE_REGEX = r#"^[^,="\\[:cntrl:]]*$"#;
+
+    /// Regex to match systemd unit names, including template instances (`name@instance.type`).
+    pub SYSTEMD_UNIT_NAME_REGEX = r"^[a-zA-Z0-9:_.\\-]+(?:@[a-zA-Z0-9:_.\\-]*)?\.(?:service|socket|device|mount|automount|swap|target|path|timer|slice|scope)$";
 }
 
 pub const SAFE_ID_FORMAT: ApiStringFormat = ApiStringFormat::Pattern(&SAFE_ID_REGEX);
@@ -301,4 +308,24 @@ fn test_regexes() {
     assert!(!ED25519_BASE64_KEY_REGEX.is_match("6zroXbjGs9sdOpr1n/M5hh+UklBxtQ90tGQDnYzJfw=="));
     // 33 bytes of data
     assert!(!ED25519_BASE64_KEY_REGEX.is_match("IiC3Nkh4Fn2ukUZUNmdK5K5CWO53Zmk/eGlKO4m6aCD/"));
+
+    assert!(PROPERTY_STRING_VALUE_REGEX.is_match(""));
+    assert!(PROPERTY_STRING_VALUE_REGEX.is_match("simple-value"));
+    assert!(PROPERTY_STRING_VALUE_REGEX.is_match("with spaces and /slashes/"));
+    assert!(PROPERTY_STRING_VALUE_REGEX.is_match("ümlaut"));
+    assert!(!PROPERTY_STRING_VALUE_REGEX.is_match("a,b"));
+    assert!(!PROPERTY_STRING_VALUE_REGEX.is_match("key=value"));
+    assert!(!PROPERTY_STRING_VALUE_REGEX.is_match("\"quoted\""));
+    assert!(!PROPERTY_STRING_VALUE_REGEX.is_match("back\\slash"));
+    assert!(!PROPERTY_STRING_VALUE_REGEX.is_match("new\nline"));
+    assert!(!PROPERTY_STRING_VALUE_REGEX.is_match("tab\tbed"));
+
+    assert!(SYSTEMD_UNIT_NAME_REGEX.is_match("proxmox-backup.service"));
+    assert!(SYSTEMD_UNIT_NAME_REGEX.is_match("getty@tty1.service"));
+    assert!(SYSTEMD_UNIT_NAME_REGEX.is_match("mnt-datastore-foo\\x2dbar.mount"));
+    assert!(SYSTEMD_UNIT_NAME_REGEX.is_match("timers.target"));
+    assert!(!SYSTEMD_UNIT_NAME_REGEX.is_match("proxmox-backup"));
+    assert!(!SYSTEMD_UNIT_NAME_REGEX.is_match("foo.invalid"));
+    assert!(!SYSTEMD_UNIT_NAME_REGEX.is_match("with space.service"));
+    assert!(!SYSTEMD_UNIT_NAME_REGEX.is_match("../evil.service"));
 }
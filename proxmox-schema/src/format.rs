@@ -341,6 +341,13 @@ pub fn get_schema_type_text(schema: &Schema, _style: ParameterDisplayStyle) -> S
             }
         }
         Schema::Boolean(_) => String::from("<boolean>"),
+        Schema::Integer(IntegerSchema {
+            allowed_values: Some(values),
+            ..
+        }) => {
+            let list: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+            list.join("|")
+        }
         Schema::Integer(integer_schema) => match (integer_schema.minimum, integer_schema.maximum) {
             (Some(min), Some(max)) => format!("<integer> ({min} - {max})"),
             (Some(min), None) => format!("<integer> ({min} - N)"),
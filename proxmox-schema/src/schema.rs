@@ -233,6 +233,8 @@ pub struct IntegerSchema {
     pub maximum: Option<i64>,
     /// Optional default.
     pub default: Option<i64>,
+    /// Optional list of the only values allowed.
+    pub allowed_values: Option<&'static [i64]>,
 }
 
 impl IntegerSchema {
@@ -242,6 +244,7 @@ impl IntegerSchema {
             default: None,
             minimum: None,
             maximum: None,
+            allowed_values: None,
         }
     }
 
@@ -265,6 +268,12 @@ impl IntegerSchema {
         self
     }
 
+    /// Restrict the value to the given list, used for integer backed enums.
+    pub const fn allowed_values(mut self, values: &'static [i64]) -> Self {
+        self.allowed_values = Some(values);
+        self
+    }
+
     pub const fn schema(self) -> Schema {
         Schema::Integer(self)
     }
@@ -290,6 +299,12 @@ impl IntegerSchema {
             }
         }
 
+        if let Some(allowed_values) = self.allowed_values {
+            if !allowed_values.contains(&value) {
+                bail!("value '{}' is not defined in the enumeration.", value);
+            }
+        }
+
         Ok(())
     }
 
@@ -2016,6 +2031,72 @@ pub fn parse_boolean(value_str: &str) -> Result<bool, Error> {
     }
 }
 
+/// Parse a boolean value from a configuration file.
+///
+/// In addition to the values accepted by [`parse_boolean`] this also understands
+/// `enabled | disabled`. Values are matched case-insensitively.
+pub fn parse_bool(value_str: &str) -> Result<bool, Error> {
+    if value_str.eq_ignore_ascii_case("enabled") {
+        return Ok(true);
+    } else if value_str.eq_ignore_ascii_case("disabled") {
+        return Ok(false);
+    }
+
+    parse_boolean(value_str).map_err(|_| {
+        format_err!(
+            "invalid boolean value '{value_str}', expected one of \
+            1/0, yes/no, on/off, true/false or enabled/disabled"
+        )
+    })
+}
+
+/// Maximum number of entries [`parse_int_ranges`] expands a list to.
+pub const MAX_INT_RANGES_ENTRIES: u64 = 64 * 1024;
+
+/// Parse a list of integers and inclusive ranges, like `1-3,5,7-9`, as used for CPU sets or
+/// index selections.
+///
+/// Returns the sorted list of all contained values without duplicates. Whitespace around the
+/// elements is ignored, inverted ranges like `5-3` are rejected. Since all values get expanded,
+/// lists with more than [`MAX_INT_RANGES_ENTRIES`] entries in total are rejected as well.
+pub fn parse_int_ranges(value_str: &str) -> Result<Vec<u64>, Error> {
+    let parse_int = |value: &str| -> Result<u64, Error> {
+        let value = value.trim();
+        value
+            .parse()
+            .map_err(|err| format_err!("invalid number '{value}' - {err}"))
+    };
+
+    let mut list = Vec::new();
+    for part in value_str.split(',') {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (parse_int(start)?, parse_int(end)?),
+            None => {
+                let value = parse_int(part)?;
+                (value, value)
+            }
+        };
+        if start > end {
+            bail!(
+                "invalid range '{}' - start is greater than end",
+                part.trim()
+            );
+        }
+
+        // `end - start` cannot overflow, but adding 1 to it could
+        let remaining = MAX_INT_RANGES_ENTRIES - list.len() as u64;
+        if end - start >= remaining {
+            bail!("too many entries in range list, at most {MAX_INT_RANGES_ENTRIES} are allowed");
+        }
+        list.extend(start..=end);
+    }
+
+    list.sort_unstable();
+    list.dedup();
+
+    Ok(list)
+}
+
 fn do_parse_parameter_strings(
     schema: ParameterSchema,
     data: &[(String, String)],
@@ -479,3 +479,66 @@ fn test_one_of_schema_with_multiple_string_variant() {
         ],
     );
 }
+
+#[test]
+fn test_parse_bool() {
+    for value in [
+        "1", "yes", "on", "true", "enabled", "YES", "On", "True", "ENABLED",
+    ] {
+        assert!(parse_bool(value).unwrap(), "{value} should be true");
+    }
+
+    for value in [
+        "0", "no", "off", "false", "disabled", "NO", "Off", "FALSE", "Disabled",
+    ] {
+        assert!(!parse_bool(value).unwrap(), "{value} should be false");
+    }
+
+    for value in ["", "2", "y", "enable", " true"] {
+        let err = parse_bool(value).unwrap_err();
+        assert!(err.to_string().contains(&format!("'{value}'")), "{err}");
+    }
+
+    // the api parser stays strict
+    assert!(parse_boolean("enabled").is_err());
+}
+
+#[test]
+fn test_parse_int_ranges() {
+    assert_eq!(
+        parse_int_ranges("1-3,5,7-9").unwrap(),
+        [1, 2, 3, 5, 7, 8, 9]
+    );
+    assert_eq!(parse_int_ranges("4").unwrap(), [4]);
+    assert_eq!(parse_int_ranges("2-2").unwrap(), [2]);
+    assert_eq!(parse_int_ranges(" 8, 0 - 1 ").unwrap(), [0, 1, 8]);
+
+    // overlapping ranges and repeated values are merged
+    assert_eq!(parse_int_ranges("5,1-4,3-6,5").unwrap(), [1, 2, 3, 4, 5, 6]);
+
+    let err = parse_int_ranges("1,5-3").unwrap_err();
+    assert!(err.to_string().contains("'5-3'"), "{err}");
+
+    // huge ranges are rejected instead of being expanded
+    for value in [
+        "0-4000000000",
+        "0-18446744073709551615",
+        "0-65535,70000-70000",
+    ] {
+        assert!(
+            parse_int_ranges(value).is_err(),
+            "'{value}' should not parse"
+        );
+    }
+    assert_eq!(
+        parse_int_ranges("0-65535").unwrap().len() as u64,
+        MAX_INT_RANGES_ENTRIES
+    );
+
+    for value in ["", "1,,2", "a", "1-", "-1", "1-2-3", "1;2"] {
+        assert!(
+            parse_int_ranges(value).is_err(),
+            "'{value}' should not parse"
+        );
+    }
+}
@@ -152,6 +152,68 @@ where
     unsafe { String::from_utf8_unchecked(out) }
 }
 
+/// Format a header line, folding it at whitespace so that no line exceeds 78 characters, as
+/// recommended by RFC 5322 Section 2.1.1.
+///
+/// Continuation lines are separated by `\r\n` and start with the whitespace the line was folded
+/// at, so unfolding the header yields the original value. Words that are longer than a line are
+/// never split. The returned string does not include the final line break.
+pub fn fold_header(name: &str, value: &str) -> String {
+    const MAX_LINE_LENGTH: usize = 78;
+
+    let mut header = format!("{name}:");
+    let mut line_length = header.len();
+
+    for (i, word) in value.split(' ').enumerate() {
+        if i > 0 && line_length + 1 + word.len() > MAX_LINE_LENGTH {
+            header.push_str("\r\n");
+            line_length = 0;
+        }
+
+        header.push(' ');
+        header.push_str(word);
+        line_length += 1 + word.len();
+    }
+
+    header
+}
+
+/// Encode a non-ASCII header value as a space separated list of RFC 2047 encoded words, which can
+/// then be folded with [`fold_header`].
+///
+/// The words stay below the limit of 75 characters per encoded word and the first one is shortened
+/// to fit after the header's `name`. All words contain a multiple of 4 base64 characters, so each
+/// of them can be decoded on its own.
+fn encode_header_words(name: &str, value: &str) -> String {
+    let encoded = proxmox_base64::encode(value);
+    let shorten_first = (name.len() + ": ".len()).next_multiple_of(4);
+
+    format_encoded_text(
+        &encoded,
+        |_| "=?utf-8?B?".into(),
+        "?=",
+        shorten_first,
+        false,
+    )
+    .replace('\n', " ")
+}
+
+/// Convert all bare `\n` line endings to `\r\n`, as required by RFC 5322.
+fn normalize_line_endings(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + text.len() / 32);
+    let mut previous = None;
+
+    for c in text.chars() {
+        if c == '\n' && previous != Some('\r') {
+            out.push('\r');
+        }
+        out.push(c);
+        previous = Some(c);
+    }
+
+    out
+}
+
 struct Recipient {
     name: Option<String>,
     email: String,
@@ -430,6 +492,16 @@ impl<'a> Mail<'a> {
         Ok(())
     }
 
+    /// Writes the formatted email, including all headers and attachments, to `writer`.
+    ///
+    /// This produces the same message that [`send`](Mail::send) pipes into `sendmail`, which is
+    /// useful to store or inspect a mail without sending it.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        let body = self.format_mail(proxmox_time::epoch_i64())?;
+        writer.write_all(body.as_bytes())?;
+        Ok(())
+    }
+
     /// Forwards an email message to a given list of recipients.
     ///
     /// `message` must be compatible with ``sendmail`` (the message is piped into stdin unmodified).
@@ -501,7 +573,7 @@ impl<'a> Mail<'a> {
             write!(mail, "\n--{file_boundary}--")?;
         }
 
-        Ok(mail)
+        Ok(normalize_line_endings(&mail))
     }
 
     fn format_header(
@@ -539,24 +611,22 @@ impl<'a> Mail<'a> {
         }
 
         if !self.subject.is_ascii() {
-            writeln!(
-                header,
-                "Subject: =?utf-8?B?{}?=",
-                proxmox_base64::encode(&self.subject)
-            )?;
+            let subject = encode_header_words("Subject", &self.subject);
+            writeln!(header, "{}", fold_header("Subject", &subject))?;
         } else {
-            writeln!(header, "Subject: {}", self.subject)?;
+            writeln!(header, "{}", fold_header("Subject", &self.subject))?;
         };
 
         if !self.mail_author.is_ascii() {
-            writeln!(
-                header,
-                "From: =?utf-8?B?{}?= <{}>",
-                proxmox_base64::encode(&self.mail_author),
+            let from = format!(
+                "{} <{}>",
+                encode_header_words("From", &self.mail_author),
                 self.mail_from
-            )?;
+            );
+            writeln!(header, "{}", fold_header("From", &from))?;
         } else {
-            writeln!(header, "From: {} <{}>", self.mail_author, self.mail_from)?;
+            let from = format!("{} <{}>", self.mail_author, self.mail_from);
+            writeln!(header, "{}", fold_header("From", &from))?;
         }
 
         let to = if self.to.len() > 1 && self.mask_participants {
@@ -579,7 +649,7 @@ impl<'a> Mail<'a> {
                 .join(", ")
         };
 
-        writeln!(header, "To: {to}")?;
+        writeln!(header, "{}", fold_header("To", &to))?;
 
         let rfc2822_date = proxmox_time::epoch_to_rfc2822(now)
             .with_context(|| "could not convert epoch to rfc2822 date")?;
@@ -759,7 +829,8 @@ Nothing too special."#,
             r#"MIME-Version: 1.0
 Subject: Subject Line
 From: Sender Name <mailfrom@example.com>
-To: Receiver Name <receiver@example.com>, two@example.com, =?utf-8?B?bcOkeCBtw7xzdGVybcOkbm4=?= <mm@example.com>
+To: Receiver Name <receiver@example.com>, two@example.com,
+ =?utf-8?B?bcOkeCBtw7xzdGVybcOkbm4=?= <mm@example.com>
 Date: Thu, 01 Jan 1970 01:00:00 +0100
 Auto-Submitted: auto-generated;
 Content-Type: text/plain;
@@ -929,6 +1000,108 @@ Content-Transfer-Encoding: base64
         )
     }
 
+    #[test]
+    fn write_mail_with_log_attachment() {
+        let log = b"2024-11-28T16:04:11+01:00: starting backup\n";
+
+        let mail = Mail::new(
+            "Sender Name",
+            "from@example.com",
+            "Backup Report",
+            "Backup finished.",
+        )
+        .with_recipient("receiver@example.com")
+        .with_html_alt("<b>Backup finished.</b>")
+        .with_attachment("backup.log", "text/plain", log);
+
+        let mut output = Vec::new();
+        mail.write_to(&mut output).expect("could not write mail");
+        let output = String::from_utf8(output).expect("mail is not valid UTF-8");
+
+        assert!(output.starts_with("Content-Type: multipart/mixed;\r\n"));
+        assert!(output.contains("Content-Type: multipart/alternative; boundary="));
+        assert!(output.contains(concat!(
+            "Content-Type: text/plain;\r\n\tname=\"backup.log\"\r\n",
+            "Content-Disposition: attachment;\r\n\tfilename*0*=UTF-8''backup.log\r\n",
+            "Content-Transfer-Encoding: base64\r\n\r\n",
+        )));
+        assert!(output.contains(&normalize_line_endings(&encode_base64_formatted(log))));
+    }
+
+    #[test]
+    fn long_subject_is_folded() {
+        let subject = "Backup job 'daily' on datastore 'store1' finished with errors - 3 of 27 \
+            groups failed, see the task log for details";
+        let folded = fold_header("Subject", subject);
+
+        assert_eq!(
+            folded,
+            "Subject: Backup job 'daily' on datastore 'store1' finished with errors - 3 of\r\n \
+            27 groups failed, see the task log for details"
+        );
+        assert!(folded.split("\r\n").all(|line| line.len() <= 78));
+        assert_eq!(folded.replace("\r\n", ""), format!("Subject: {subject}"));
+
+        // short headers and overlong words are left alone
+        assert_eq!(fold_header("To", "root@pam"), "To: root@pam");
+        let word = "x".repeat(100);
+        assert_eq!(fold_header("Subject", &word), format!("Subject: {word}"));
+
+        let mail = Mail::new("Sender", "from@example.com", subject, "body")
+            .with_recipient("receiver@example.com");
+        let formatted = mail.format_mail(0).expect("could not format mail");
+        assert!(formatted.starts_with(&format!("{folded}\r\nFrom: ")));
+    }
+
+    #[test]
+    fn long_non_ascii_subject_is_folded() {
+        let subject = "Sicherungsauftrag 'täglich' auf Datenspeicher 'speicher1' mit Fehlern \
+            beendet - 3 von 27 Gruppen fehlgeschlagen, Details siehe Aufgabenprotokoll";
+        let author = "Proxmox Backup Server auf dem Knoten 'prüfsystem-01' im Rechenzentrum";
+
+        let mail = Mail::new(author, "from@example.com", subject, "body")
+            .with_recipient("receiver@example.com");
+        let formatted = mail.format_mail(0).expect("could not format mail");
+        let (header, _) = formatted.split_once("\r\nTo: ").unwrap();
+        assert!(header.split("\r\n").all(|line| line.len() <= 78));
+        let (_, header) = header.split_once("Subject: ").unwrap();
+
+        let (subject_header, from_header) = header.split_once("\r\nFrom: ").unwrap();
+        assert!(subject_header.contains("\r\n "));
+
+        let decode = |value: &str| {
+            let mut decoded = Vec::new();
+            for word in value.split_whitespace() {
+                assert!(word.len() <= 75, "encoded word too long: {word}");
+                let word = word
+                    .strip_prefix("=?utf-8?B?")
+                    .and_then(|word| word.strip_suffix("?="))
+                    .unwrap();
+                decoded.extend(proxmox_base64::decode(word).unwrap());
+            }
+            String::from_utf8(decoded).unwrap()
+        };
+
+        assert_eq!(decode(subject_header), subject);
+        let from = from_header.strip_suffix(" <from@example.com>").unwrap();
+        assert_eq!(decode(from), author);
+    }
+
+    #[test]
+    fn body_line_endings_are_normalized() {
+        let mail = Mail::new(
+            "Sender",
+            "from@example.com",
+            "Subject",
+            "first line\nsecond line\r\nthird line\n",
+        )
+        .with_recipient("receiver@example.com");
+        let formatted = mail.format_mail(0).expect("could not format mail");
+
+        assert!(formatted.ends_with("\r\n\r\nfirst line\r\nsecond line\r\nthird line\r\n"));
+        assert!(!formatted.replace("\r\n", "").contains('\n'));
+    }
+
     #[test]
     fn multipart_plain_text_html_alternative_attachments() {
         let bin: [u8; 62] = [
@@ -247,3 +247,527 @@ pub mod byte_array_as_base64 {
         <T as ByteArrayAsBase64<N>>::de::<'de, D>(deserializer)
     }
 }
+
+/// Serialize [`Duration`](std::time::Duration) as an integer number of whole seconds.
+///
+/// Sub-second precision is discarded when serializing. Deserialization only accepts unsigned
+/// integers, floating point values are rejected instead of being rounded silently.
+///
+/// Usage example:
+/// ```
+/// use std::time::Duration;
+///
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Deserialize, PartialEq, Serialize)]
+/// struct Foo {
+///     #[serde(with = "proxmox_serde::duration_as_secs")]
+///     timeout: Duration,
+/// }
+///
+/// let obj = Foo { timeout: Duration::from_secs(90) };
+/// let json = serde_json::to_string(&obj).unwrap();
+/// assert_eq!(json, r#"{"timeout":90}"#);
+///
+/// let deserialized: Foo = serde_json::from_str(&json).unwrap();
+/// assert_eq!(obj, deserialized);
+///
+/// assert!(serde_json::from_str::<Foo>(r#"{"timeout":1.5}"#).is_err());
+/// ```
+pub mod duration_as_secs {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(duration.as_secs())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        u64::deserialize(deserializer).map(Duration::from_secs)
+    }
+}
+
+/// Deserialize either a single string or a sequence of strings into a `Vec<String>`.
+///
+/// Serialization always produces a sequence.
+///
+/// Usage example:
+/// ```
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Deserialize, PartialEq, Serialize)]
+/// struct Foo {
+///     #[serde(with = "proxmox_serde::string_or_seq")]
+///     names: Vec<String>,
+/// }
+///
+/// let single: Foo = serde_json::from_str(r#"{"names":"a"}"#).unwrap();
+/// assert_eq!(single.names, ["a"]);
+///
+/// let multiple: Foo = serde_json::from_str(r#"{"names":["a","b"]}"#).unwrap();
+/// assert_eq!(multiple.names, ["a", "b"]);
+///
+/// assert_eq!(serde_json::to_string(&single).unwrap(), r#"{"names":["a"]}"#);
+/// ```
+pub mod string_or_seq {
+    use std::fmt;
+
+    use serde::de::{SeqAccess, Visitor};
+    use serde::{Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(data: &[String], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        data.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct StringOrSeqVisitor;
+
+        impl<'de> Visitor<'de> for StringOrSeqVisitor {
+            type Value = Vec<String>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string or a list of strings")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(vec![value.to_string()])
+            }
+
+            fn visit_string<E: serde::de::Error>(self, value: String) -> Result<Self::Value, E> {
+                Ok(vec![value])
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut list = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(value) = seq.next_element()? {
+                    list.push(value);
+                }
+                Ok(list)
+            }
+        }
+
+        deserializer.deserialize_any(StringOrSeqVisitor)
+    }
+}
+
+/// Serialize a `PathBuf` as string, expanding a leading `~/` to the current user's home directory
+/// (`$HOME`) when deserializing.
+///
+/// Only a leading `~` (either alone or followed by `/`) is expanded, `~user` and tildes anywhere
+/// else are kept verbatim. When serializing, paths below `$HOME` are written with a leading `~/`
+/// again, so a configuration round-trips unchanged.
+///
+/// Usage example:
+/// ```
+/// use std::path::{Path, PathBuf};
+///
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Deserialize, PartialEq, Serialize)]
+/// struct Foo {
+///     #[serde(with = "proxmox_serde::path_with_tilde")]
+///     path: PathBuf,
+/// }
+///
+/// // SAFETY: doc tests are single threaded
+/// unsafe { std::env::set_var("HOME", "/home/user") };
+///
+/// let obj: Foo = serde_json::from_str(r#"{"path":"~/.config/foo.cfg"}"#).unwrap();
+/// assert_eq!(obj.path, Path::new("/home/user/.config/foo.cfg"));
+/// assert_eq!(
+///     serde_json::to_string(&obj).unwrap(),
+///     r#"{"path":"~/.config/foo.cfg"}"#,
+/// );
+///
+/// let obj: Foo = serde_json::from_str(r#"{"path":"/etc/foo.cfg"}"#).unwrap();
+/// assert_eq!(obj.path, Path::new("/etc/foo.cfg"));
+/// assert_eq!(serde_json::to_string(&obj).unwrap(), r#"{"path":"/etc/foo.cfg"}"#);
+///
+/// let obj: Foo = serde_json::from_str(r#"{"path":"~"}"#).unwrap();
+/// assert_eq!(obj.path, Path::new("/home/user"));
+///
+/// let obj: Foo = serde_json::from_str(r#"{"path":"/srv/~/foo"}"#).unwrap();
+/// assert_eq!(obj.path, Path::new("/srv/~/foo"));
+/// ```
+pub mod path_with_tilde {
+    use std::path::{Path, PathBuf};
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    fn home() -> Option<PathBuf> {
+        std::env::var_os("HOME")
+            .filter(|home| !home.is_empty())
+            .map(PathBuf::from)
+    }
+
+    pub fn serialize<S>(path: &Path, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::Error;
+
+        let path = path
+            .to_str()
+            .ok_or_else(|| S::Error::custom(format!("path {path:?} is not valid UTF-8")))?;
+
+        if let Some(home) = home().as_deref().and_then(Path::to_str) {
+            let home = home.trim_end_matches('/');
+            if path == home {
+                return serializer.serialize_str("~");
+            }
+            if let Some(rest) = path.strip_prefix(home).and_then(|p| p.strip_prefix('/')) {
+                return serializer.serialize_str(&format!("~/{rest}"));
+            }
+        }
+
+        serializer.serialize_str(path)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<PathBuf, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let path = String::deserialize(deserializer)?;
+
+        let rest = match path.strip_prefix('~') {
+            Some("") => "",
+            Some(rest) if rest.starts_with('/') => rest.trim_start_matches('/'),
+            _ => return Ok(PathBuf::from(path)),
+        };
+
+        let mut expanded = home()
+            .ok_or_else(|| D::Error::custom(format!("cannot expand '{path}', $HOME not set")))?;
+        if !rest.is_empty() {
+            expanded.push(rest);
+        }
+        Ok(expanded)
+    }
+}
+
+/// Serialize bytes as lowercase hex string.
+///
+/// Deserialization accepts both upper and lower case digits and works for any type implementing
+/// [`hex::FromHex`], like `Vec<u8>` or `[u8; N]`, where the latter also enforces the length.
+///
+/// Usage example:
+/// ```
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Deserialize, PartialEq, Serialize)]
+/// struct Foo {
+///     #[serde(with = "proxmox_serde::bytes_as_hex")]
+///     digest: [u8; 4],
+///     #[serde(with = "proxmox_serde::bytes_as_hex")]
+///     data: Vec<u8>,
+/// }
+///
+/// let obj = Foo {
+///     digest: [0xde, 0xad, 0xbe, 0xef],
+///     data: vec![0x01, 0xff],
+/// };
+/// let json = serde_json::to_string(&obj).unwrap();
+/// assert_eq!(json, r#"{"digest":"deadbeef","data":"01ff"}"#);
+///
+/// let deserialized: Foo = serde_json::from_str(&json).unwrap();
+/// assert_eq!(obj, deserialized);
+///
+/// // odd length
+/// assert!(serde_json::from_str::<Foo>(r#"{"digest":"deadbee","data":""}"#).is_err());
+/// // wrong length for the array
+/// assert!(serde_json::from_str::<Foo>(r#"{"digest":"deadbeef00","data":""}"#).is_err());
+/// ```
+pub mod bytes_as_hex {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S, T>(data: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsRef<[u8]>,
+    {
+        serializer.serialize_str(&hex::encode(data))
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: hex::FromHex,
+        T::Error: std::fmt::Display,
+    {
+        use serde::de::Error;
+
+        let string = String::deserialize(deserializer)?;
+        T::from_hex(string.as_bytes()).map_err(|err| D::Error::custom(format!("hex decode: {err}")))
+    }
+}
+
+/// Serialize an `IpAddr` as string in its canonical text form.
+///
+/// Unlike serde's own implementation this always uses the text form, also for non-human-readable
+/// formats, and reports the malformed input when deserialization fails.
+///
+/// Usage example:
+/// ```
+/// use std::net::{IpAddr, Ipv6Addr};
+///
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Deserialize, PartialEq, Serialize)]
+/// struct Foo {
+///     #[serde(with = "proxmox_serde::ip_addr_as_string")]
+///     address: IpAddr,
+/// }
+///
+/// let obj = Foo {
+///     address: IpAddr::V6(Ipv6Addr::new(0xfd00, 0, 0, 0, 0, 0, 0, 1)),
+/// };
+/// let json = serde_json::to_string(&obj).unwrap();
+/// assert_eq!(json, r#"{"address":"fd00::1"}"#);
+///
+/// let deserialized: Foo = serde_json::from_str(&json).unwrap();
+/// assert_eq!(obj, deserialized);
+///
+/// assert!(serde_json::from_str::<Foo>(r#"{"address":"192.168.0.256"}"#).is_err());
+/// ```
+pub mod ip_addr_as_string {
+    use std::net::IpAddr;
+
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S>(address: &IpAddr, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(address)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<IpAddr, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::parse_address_string(deserializer, "IP address")
+    }
+}
+
+/// Serialize a `SocketAddr` as string in its canonical text form, e.g. `[fd00::1]:8007`.
+///
+/// Unlike serde's own implementation this always uses the text form, also for non-human-readable
+/// formats, and reports the malformed input when deserialization fails.
+///
+/// Usage example:
+/// ```
+/// use std::net::{Ipv6Addr, SocketAddr, SocketAddrV6};
+///
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Deserialize, PartialEq, Serialize)]
+/// struct Foo {
+///     #[serde(with = "proxmox_serde::socket_addr_as_string")]
+///     listen: SocketAddr,
+/// }
+///
+/// let obj = Foo {
+///     listen: SocketAddr::V6(SocketAddrV6::new(
+///         Ipv6Addr::new(0xfd00, 0, 0, 0, 0, 0, 0, 1),
+///         8007,
+///         0,
+///         0,
+///     )),
+/// };
+/// let json = serde_json::to_string(&obj).unwrap();
+/// assert_eq!(json, r#"{"listen":"[fd00::1]:8007"}"#);
+///
+/// let deserialized: Foo = serde_json::from_str(&json).unwrap();
+/// assert_eq!(obj, deserialized);
+///
+/// // the port is required, and IPv6 addresses need brackets
+/// assert!(serde_json::from_str::<Foo>(r#"{"listen":"[fd00::1]"}"#).is_err());
+/// assert!(serde_json::from_str::<Foo>(r#"{"listen":"fd00::1:8007"}"#).is_err());
+/// ```
+pub mod socket_addr_as_string {
+    use std::net::SocketAddr;
+
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S>(address: &SocketAddr, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(address)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<SocketAddr, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::parse_address_string(deserializer, "socket address")
+    }
+}
+
+/// Serialize an `f64` while rejecting NaN and infinite values.
+///
+/// Non-finite values cannot be represented in JSON (`serde_json` silently turns them into
+/// `null`), so both serializing and deserializing them fails with an error instead.
+///
+/// Usage example:
+/// ```
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Deserialize, PartialEq, Serialize)]
+/// struct Foo {
+///     #[serde(with = "proxmox_serde::finite_f64")]
+///     load: f64,
+/// }
+///
+/// let obj = Foo { load: 0.75 };
+/// let json = serde_json::to_string(&obj).unwrap();
+/// assert_eq!(json, r#"{"load":0.75}"#);
+///
+/// let deserialized: Foo = serde_json::from_str(&json).unwrap();
+/// assert_eq!(obj, deserialized);
+///
+/// assert!(serde_json::to_string(&Foo { load: f64::NAN }).is_err());
+/// assert!(serde_json::to_string(&Foo { load: f64::INFINITY }).is_err());
+///
+/// // JSON cannot express NaN, but other formats can
+/// use serde::de::value::{Error, F64Deserializer};
+/// let nan = F64Deserializer::<Error>::new(f64::NAN);
+/// assert!(proxmox_serde::finite_f64::deserialize(nan).is_err());
+/// ```
+pub mod finite_f64 {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &f64, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::Error;
+
+        if !value.is_finite() {
+            return Err(S::Error::custom(format!(
+                "cannot serialize non-finite number {value}"
+            )));
+        }
+        serializer.serialize_f64(*value)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<f64, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let value = f64::deserialize(deserializer)?;
+        if !value.is_finite() {
+            return Err(D::Error::custom(format!(
+                "expected a finite number, got {value}"
+            )));
+        }
+        Ok(value)
+    }
+}
+
+/// Serialize a list of key-value pairs as a map, keeping the order of its entries.
+///
+/// When deserializing, the entries are collected in the order they appear in the input, which a
+/// `HashMap` would lose. Duplicate keys are kept as they are.
+///
+/// Usage example:
+/// ```
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Deserialize, PartialEq, Serialize)]
+/// struct Foo {
+///     #[serde(with = "proxmox_serde::ordered_map_as_pairs")]
+///     options: Vec<(String, u32)>,
+/// }
+///
+/// let json = r#"{"options":{"zeta":1,"alpha":2,"mid":3}}"#;
+/// let obj: Foo = serde_json::from_str(json).unwrap();
+/// assert_eq!(
+///     obj.options,
+///     [
+///         ("zeta".to_string(), 1),
+///         ("alpha".to_string(), 2),
+///         ("mid".to_string(), 3),
+///     ],
+/// );
+///
+/// assert_eq!(serde_json::to_string(&obj).unwrap(), json);
+/// ```
+pub mod ordered_map_as_pairs {
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    use serde::de::{MapAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S, K, V>(pairs: &[(K, V)], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        K: Serialize,
+        V: Serialize,
+    {
+        serializer.collect_map(pairs.iter().map(|(key, value)| (key, value)))
+    }
+
+    pub fn deserialize<'de, D, K, V>(deserializer: D) -> Result<Vec<(K, V)>, D::Error>
+    where
+        D: Deserializer<'de>,
+        K: Deserialize<'de>,
+        V: Deserialize<'de>,
+    {
+        struct PairVisitor<K, V>(PhantomData<(K, V)>);
+
+        impl<'de, K, V> Visitor<'de> for PairVisitor<K, V>
+        where
+            K: Deserialize<'de>,
+            V: Deserialize<'de>,
+        {
+            type Value = Vec<(K, V)>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut pairs = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some(entry) = map.next_entry()? {
+                    pairs.push(entry);
+                }
+                Ok(pairs)
+            }
+        }
+
+        deserializer.deserialize_map(PairVisitor(PhantomData))
+    }
+}
+
+fn parse_address_string<'de, D, T>(deserializer: D, what: &str) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    use serde::Deserialize;
+    use serde::de::Error;
+
+    let string = String::deserialize(deserializer)?;
+    string
+        .parse()
+        .map_err(|err| D::Error::custom(format!("invalid {what} '{string}' - {err}")))
+}
@@ -6,9 +6,13 @@ use nix::NixPath;
 use nix::sys::stat::Mode;
 use nix::{fcntl::OFlag, sys::stat};
 
-use std::os::unix::io::{FromRawFd, OwnedFd, RawFd};
+use std::os::unix::io::{AsFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
 
-use nix::fcntl::{F_GETFD, F_SETFD, FdFlag, fcntl};
+use anyhow::{Context, Error};
+use nix::errno::Errno;
+use nix::fcntl::{F_GETFD, F_GETFL, F_SETFD, F_SETFL, FcntlArg, FdFlag, fcntl};
+use nix::sys::socket::{getsockopt, sockopt::PeerCredentials};
+use nix::unistd::dup2;
 
 /// Change the `O_CLOEXEC` flag of an existing file descriptor.
 pub fn fd_change_cloexec(fd: RawFd, on: bool) -> Result<(), anyhow::Error> {
@@ -23,6 +27,109 @@ pub fn change_cloexec(fd: RawFd, on: bool) -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+/// `fcntl` wrapper retrying on `EINTR`.
+fn fcntl_retry<'a>(fd: RawFd, arg: impl Fn() -> FcntlArg<'a>) -> Result<i32, nix::Error> {
+    loop {
+        match fcntl(fd, arg()) {
+            Err(Errno::EINTR) => continue,
+            other => return other,
+        }
+    }
+}
+
+/// Set or clear the `O_NONBLOCK` flag of an existing file descriptor.
+///
+/// Returns whether the flag was previously set.
+pub fn set_nonblocking(fd: RawFd, on: bool) -> Result<bool, Error> {
+    let mut flags = OFlag::from_bits_retain(
+        fcntl_retry(fd, || F_GETFL).with_context(|| format!("failed to get flags of fd {fd}"))?,
+    );
+    let previous = flags.contains(OFlag::O_NONBLOCK);
+    flags.set(OFlag::O_NONBLOCK, on);
+    fcntl_retry(fd, || F_SETFL(flags))
+        .with_context(|| format!("failed to change O_NONBLOCK flag of fd {fd}"))?;
+    Ok(previous)
+}
+
+/// Set or clear the `FD_CLOEXEC` flag of an existing file descriptor.
+///
+/// Returns whether the flag was previously set.
+pub fn set_cloexec(fd: RawFd, on: bool) -> Result<bool, Error> {
+    let mut flags = FdFlag::from_bits_retain(
+        fcntl_retry(fd, || F_GETFD)
+            .with_context(|| format!("failed to get descriptor flags of fd {fd}"))?,
+    );
+    let previous = flags.contains(FdFlag::FD_CLOEXEC);
+    flags.set(FdFlag::FD_CLOEXEC, on);
+    fcntl_retry(fd, || F_SETFD(flags))
+        .with_context(|| format!("failed to change FD_CLOEXEC flag of fd {fd}"))?;
+    Ok(previous)
+}
+
+/// Get the credentials of the peer connected to a unix socket via `SO_PEERCRED`.
+///
+/// Returns the `(uid, gid, pid)` of the peer process at the time it connected.
+pub fn peer_cred(fd: RawFd) -> Result<(u32, u32, i32), Error> {
+    // SAFETY: the fd is only borrowed for the duration of the `getsockopt` call.
+    let fd = unsafe { BorrowedFd::borrow_raw(fd) };
+    let cred = getsockopt(&fd, PeerCredentials)
+        .with_context(|| format!("failed to get peer credentials of fd {}", fd.as_raw_fd()))?;
+    Ok((cred.uid(), cred.gid(), cred.pid()))
+}
+
+/// Temporarily redirect a file descriptor for the lifetime of the guard.
+///
+/// On construction, a copy of the original file descriptor is put aside and the replacement is
+/// installed in its place via `dup2`. When the guard is dropped, the original is restored. This
+/// can be used to capture the output written to `stderr` within a scope, for example.
+pub struct RedirectGuard {
+    target: RawFd,
+    saved: Option<OwnedFd>,
+}
+
+impl RedirectGuard {
+    /// Redirect `target` to `replacement`.
+    ///
+    /// The replacement is duplicated, so it may be closed while the guard is alive.
+    pub fn new<F: AsFd + ?Sized>(target: RawFd, replacement: &F) -> Result<Self, Error> {
+        let saved = fcntl_retry(target, || FcntlArg::F_DUPFD_CLOEXEC(0))
+            .with_context(|| format!("failed to duplicate fd {target}"))?;
+        // SAFETY: `F_DUPFD_CLOEXEC` returned a new file descriptor we now own.
+        let saved = unsafe { OwnedFd::from_raw_fd(saved) };
+
+        dup2(replacement.as_fd().as_raw_fd(), target)
+            .with_context(|| format!("failed to redirect fd {target}"))?;
+
+        Ok(Self {
+            target,
+            saved: Some(saved),
+        })
+    }
+
+    /// Restore the original file descriptor, reporting errors instead of ignoring them like the
+    /// `Drop` implementation does.
+    pub fn restore(mut self) -> Result<(), Error> {
+        self.do_restore()
+    }
+
+    fn do_restore(&mut self) -> Result<(), Error> {
+        match self.saved.take() {
+            Some(saved) => {
+                dup2(saved.as_raw_fd(), self.target)
+                    .with_context(|| format!("failed to restore fd {}", self.target))?;
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for RedirectGuard {
+    fn drop(&mut self) {
+        let _ = self.do_restore();
+    }
+}
+
 pub(crate) fn cwd() -> Result<OwnedFd, nix::Error> {
     open(".", crate::fs::DIR_FLAGS, stat::Mode::empty())
 }
@@ -42,3 +149,97 @@ where
     nix::fcntl::openat(Some(dirfd.as_raw_fd()), path, oflag, mode)
         .map(|fd| unsafe { OwnedFd::from_raw_fd(fd) })
 }
+
+#[cfg(test)]
+mod test {
+    use std::os::unix::io::AsRawFd;
+
+    use nix::fcntl::{F_GETFD, F_GETFL, FdFlag, OFlag, fcntl};
+
+    use super::{RedirectGuard, peer_cred, set_cloexec, set_nonblocking};
+
+    #[test]
+    fn test_set_nonblocking() {
+        let (read, _write) = crate::pipe().unwrap();
+        let fd = read.as_raw_fd();
+        let is_nonblocking =
+            || OFlag::from_bits_retain(fcntl(fd, F_GETFL).unwrap()).contains(OFlag::O_NONBLOCK);
+
+        assert!(!is_nonblocking());
+        assert!(!set_nonblocking(fd, true).unwrap());
+        assert!(is_nonblocking());
+        assert!(set_nonblocking(fd, true).unwrap());
+        assert!(set_nonblocking(fd, false).unwrap());
+        assert!(!is_nonblocking());
+    }
+
+    #[test]
+    fn test_set_cloexec() {
+        let (read, _write) = crate::pipe().unwrap();
+        let fd = read.as_raw_fd();
+        let is_cloexec =
+            || FdFlag::from_bits_retain(fcntl(fd, F_GETFD).unwrap()).contains(FdFlag::FD_CLOEXEC);
+
+        assert!(is_cloexec());
+        assert!(set_cloexec(fd, false).unwrap());
+        assert!(!is_cloexec());
+        assert!(!set_cloexec(fd, false).unwrap());
+        assert!(!set_cloexec(fd, true).unwrap());
+        assert!(is_cloexec());
+    }
+
+    #[test]
+    fn test_peer_cred() {
+        use nix::sys::socket::{AddressFamily, SockFlag, SockType, socketpair};
+        use nix::unistd::{Gid, Pid, Uid};
+
+        let (first, _second) = socketpair(
+            AddressFamily::Unix,
+            SockType::Stream,
+            None,
+            SockFlag::SOCK_CLOEXEC,
+        )
+        .unwrap();
+
+        let (uid, gid, pid) = peer_cred(first.as_raw_fd()).unwrap();
+        assert_eq!(uid, Uid::current().as_raw());
+        assert_eq!(gid, Gid::current().as_raw());
+        assert_eq!(pid, Pid::this().as_raw());
+
+        let (read, _write) = crate::pipe().unwrap();
+        assert!(peer_cred(read.as_raw_fd()).is_err());
+    }
+
+    #[test]
+    fn test_redirect_guard() {
+        use std::io::Read;
+
+        use nix::sys::stat::fstat;
+
+        let identity = |fd| {
+            let stat = fstat(fd).unwrap();
+            (stat.st_dev, stat.st_ino)
+        };
+        let original = identity(2);
+
+        let (read, write) = crate::pipe().unwrap();
+        {
+            let _guard = RedirectGuard::new(2, &write).unwrap();
+            assert_eq!(identity(2), identity(write.as_raw_fd()));
+            nix::unistd::write(std::io::stderr(), b"captured").unwrap();
+        }
+        assert_eq!(identity(2), original);
+
+        drop(write);
+        let mut captured = String::new();
+        std::fs::File::from(read)
+            .read_to_string(&mut captured)
+            .unwrap();
+        assert_eq!(captured, "captured");
+
+        let (_read, write) = crate::pipe().unwrap();
+        let guard = RedirectGuard::new(2, &write).unwrap();
+        guard.restore().unwrap();
+        assert_eq!(identity(2), original);
+    }
+}
@@ -1,6 +1,7 @@
 use std::ffi::OsString;
 use std::os::fd::FromRawFd;
 use std::os::unix::ffi::OsStringExt;
+use std::os::unix::fs::MetadataExt;
 use std::os::unix::io::{AsRawFd, OwnedFd};
 use std::path::{Path, PathBuf};
 
@@ -242,6 +243,41 @@ pub fn make_tmp_dir<P: AsRef<Path>>(
     Ok(path)
 }
 
+/// Compute the total size of a file or directory tree.
+///
+/// Directories are traversed recursively, symbolic links are never followed. By default the
+/// apparent size (`st_size`) of all non-directory entries is summed up. If `on_disk` is set, the
+/// allocated size (`st_blocks * 512`) of every entry, including the directories themselves, is used
+/// instead, similar to what `du` reports.
+///
+/// Errors include the path of the entry which could not be accessed.
+pub fn du<P: AsRef<Path>>(path: P, on_disk: bool) -> Result<u64, Error> {
+    let path = path.as_ref();
+
+    let metadata = std::fs::symlink_metadata(path)
+        .map_err(|err| format_err!("failed to stat {path:?} - {err}"))?;
+
+    let mut size = if on_disk {
+        metadata.blocks() * 512
+    } else if metadata.is_dir() {
+        0
+    } else {
+        metadata.len()
+    };
+
+    if metadata.is_dir() {
+        let entries = std::fs::read_dir(path)
+            .map_err(|err| format_err!("failed to read directory {path:?} - {err}"))?;
+        for entry in entries {
+            let entry =
+                entry.map_err(|err| format_err!("failed to read directory {path:?} - {err}"))?;
+            size += du(entry.path(), on_disk)?;
+        }
+    }
+
+    Ok(size)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -276,4 +312,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_du() -> Result<(), Error> {
+        let path = make_tmp_dir("/tmp", None)?;
+
+        std::fs::write(path.join("a"), [0u8; 100])?;
+        std::fs::create_dir_all(path.join("sub/nested"))?;
+        std::fs::write(path.join("sub/b"), [0u8; 2000])?;
+        std::fs::write(path.join("sub/nested/c"), [0u8; 5000])?;
+        std::os::unix::fs::symlink("/usr", path.join("sub/link"))?;
+
+        let apparent = du(&path, false)?;
+        assert_eq!(apparent, 7100 + "/usr".len() as u64);
+        assert_eq!(du(path.join("sub/nested"), false)?, 5000);
+        assert_eq!(du(path.join("a"), false)?, 100);
+
+        let on_disk = du(&path, true)?;
+        assert!(on_disk > 0);
+
+        let err = du(path.join("missing"), false).unwrap_err();
+        assert!(err.to_string().contains("missing"));
+
+        std::fs::remove_dir_all(&path)?;
+
+        Ok(())
+    }
 }
@@ -1,7 +1,10 @@
 use std::fs::File;
+#[cfg(feature = "crypt")]
+use std::io::Read;
 use std::io::{self, BufRead, BufReader, Write};
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 #[cfg(feature = "timer")]
 use std::time::Duration;
 
@@ -147,10 +150,30 @@ pub fn make_tmp_file<P: AsRef<Path>>(
 ) -> Result<(File, PathBuf), Error> {
     let path = path.as_ref();
 
-    // use mkstemp here, because it works with different processes, threads, even tokio tasks
     let mut template = path.to_owned();
     template.set_extension("tmp_XXXXXX");
-    let (mut file, tmp_path) = match mkostemp(&template, OFlag::O_CLOEXEC) {
+    make_tmp_file_from_template(&template, options)
+}
+
+/// Create a uniquely named temporary file inside `directory`, analogous to
+/// [`make_tmp_dir`](crate::fs::make_tmp_dir).
+///
+/// The file is created exclusively and has the permissions and ownership set in `options`
+/// applied. This allows staging content on the same file system as its final destination, so it
+/// can be renamed into place atomically. If applying the options fails, the file is removed again.
+pub fn make_tmp_file_in<P: AsRef<Path>>(
+    directory: P,
+    options: CreateOptions,
+) -> Result<(File, PathBuf), Error> {
+    make_tmp_file_from_template(&directory.as_ref().join("tmp_XXXXXX"), options)
+}
+
+fn make_tmp_file_from_template(
+    template: &Path,
+    options: CreateOptions,
+) -> Result<(File, PathBuf), Error> {
+    // use mkstemp here, because it works with different processes, threads, even tokio tasks
+    let (mut file, tmp_path) = match mkostemp(template, OFlag::O_CLOEXEC) {
         Ok((fd, path)) => (unsafe { File::from_raw_fd(fd) }, path),
         Err(err) => bail!("mkstemp {:?} failed: {}", template, err),
     };
@@ -206,6 +229,42 @@ pub fn replace_file<P: AsRef<Path>>(
     Ok(())
 }
 
+/// Atomically create or replace a symlink at `link` pointing to `target`.
+///
+/// The symlink is first created under a temporary name in the same directory and then renamed
+/// over `link`, so an existing link is never missing while it is being replaced.
+pub fn symlink_atomic(target: &Path, link: &Path) -> Result<(), Error> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let file_name = link
+        .file_name()
+        .ok_or_else(|| format_err!("invalid symlink path {link:?}"))?;
+
+    let tmp_path = loop {
+        let mut tmp_name = std::ffi::OsString::from(".");
+        tmp_name.push(file_name);
+        tmp_name.push(format!(
+            ".tmp_{}_{}",
+            unistd::getpid(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let tmp_path = link.with_file_name(tmp_name);
+
+        match std::os::unix::fs::symlink(target, &tmp_path) {
+            Ok(()) => break tmp_path,
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => bail!("failed to create symlink {tmp_path:?} - {err}"),
+        }
+    };
+
+    if let Err(err) = std::fs::rename(&tmp_path, link) {
+        let _ = unistd::unlink(&tmp_path);
+        bail!("Atomic rename failed for symlink {link:?} - {err}");
+    }
+
+    Ok(())
+}
+
 /// Like open(2), but allows setting initial data, perm, owner and group
 ///
 /// Since we need to initialize the file, we also need a solid slow
@@ -466,3 +525,144 @@ pub fn file_get_non_comment_lines<P: AsRef<Path>>(
         Err(err) => Some(Err(err)),
     }))
 }
+
+/// Compute the SHA-256 digest of a file's contents.
+///
+/// The file is read in chunks, so this works for large files without loading them into memory.
+#[cfg(feature = "crypt")]
+pub fn file_sha256<P: AsRef<Path>>(path: P) -> Result<[u8; 32], Error> {
+    let path = path.as_ref();
+
+    let mut file =
+        File::open(path).map_err(|err| format_err!("error opening {:?}: {}", path, err))?;
+
+    let mut hasher = openssl::sha::Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(got) => hasher.update(&buffer[..got]),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => bail!("error reading {:?}: {}", path, err),
+        }
+    }
+
+    Ok(hasher.finish())
+}
+
+/// Compute the SHA-256 digest of a file's contents as lowercase hex string.
+///
+/// See [`file_sha256`].
+#[cfg(feature = "crypt")]
+pub fn file_sha256_hex<P: AsRef<Path>>(path: P) -> Result<String, Error> {
+    Ok(hex::encode(file_sha256(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+
+    use super::*;
+    use crate::fs::make_tmp_dir;
+
+    #[test]
+    fn test_symlink_atomic() -> Result<(), Error> {
+        let dir = make_tmp_dir("/tmp", None)?;
+        let link = dir.join("current");
+
+        symlink_atomic(Path::new("v1"), &link)?;
+        assert_eq!(std::fs::read_link(&link)?, Path::new("v1"));
+
+        symlink_atomic(Path::new("v2"), &link)?;
+        assert_eq!(std::fs::read_link(&link)?, Path::new("v2"));
+
+        // best effort check that the link never goes missing while being replaced
+        let done = Arc::new(AtomicBool::new(false));
+        let watcher = {
+            let done = Arc::clone(&done);
+            let link = link.clone();
+            std::thread::spawn(move || {
+                while !done.load(Ordering::Acquire) {
+                    if std::fs::read_link(&link).is_err() {
+                        return false;
+                    }
+                }
+                true
+            })
+        };
+        for i in 0..1000 {
+            symlink_atomic(Path::new(&format!("v{i}")), &link)?;
+        }
+        done.store(true, Ordering::Release);
+        assert!(
+            watcher.join().unwrap(),
+            "symlink was missing during replacement"
+        );
+        assert_eq!(std::fs::read_link(&link)?, Path::new("v999"));
+
+        // no temporary links are left behind
+        assert_eq!(std::fs::read_dir(&dir)?.count(), 1);
+
+        std::fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "crypt")]
+    #[test]
+    fn test_file_sha256() -> Result<(), Error> {
+        let dir = make_tmp_dir("/tmp", None)?;
+        let path = dir.join("data");
+
+        std::fs::write(&path, b"abc")?;
+        assert_eq!(
+            file_sha256_hex(&path)?,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        std::fs::write(&path, b"")?;
+        assert_eq!(
+            hex::encode(file_sha256(&path)?),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+
+        // larger than a single read chunk
+        let data = vec![0x5au8; 200 * 1024];
+        std::fs::write(&path, &data)?;
+        assert_eq!(file_sha256(&path)?, openssl::sha::sha256(&data));
+
+        assert!(file_sha256(dir.join("missing")).is_err());
+
+        std::fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_make_tmp_file_in() -> Result<(), Error> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = make_tmp_dir("/tmp", None)?;
+        let options = CreateOptions::new().perm(stat::Mode::from_bits_truncate(0o640));
+
+        let (mut first, first_path) = make_tmp_file_in(&dir, options)?;
+        let (_second, second_path) = make_tmp_file_in(&dir, options)?;
+
+        assert_ne!(first_path, second_path);
+        for path in [&first_path, &second_path] {
+            assert_eq!(path.parent(), Some(dir.as_path()));
+            let metadata = std::fs::metadata(path)?;
+            assert!(metadata.is_file());
+            assert_eq!(metadata.permissions().mode() & 0o7777, 0o640);
+        }
+
+        first.write_all(b"staged")?;
+        assert_eq!(std::fs::read(&first_path)?, b"staged");
+
+        assert!(make_tmp_file_in(dir.join("missing"), options).is_err());
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
@@ -114,6 +114,28 @@ pub fn read_subdir<P: ?Sized + nix::NixPath>(dirfd: RawFd, path: &P) -> nix::Res
     Ok(ReadDir { iter, dir_fd: fd })
 }
 
+/// Iterate over the entries of a directory, skipping `.` and `..`, which match `predicate`.
+///
+/// `path` is relative to the current working directory if it is not absolute. The entries' file
+/// types as reported by `readdir` (`d_type`) are available via `file_type()` without additional
+/// `stat` calls, use [`get_file_type`] as fallback for file systems which do not provide them.
+pub fn read_dir_filtered<P, F>(
+    path: &P,
+    predicate: F,
+) -> Result<impl Iterator<Item = Result<ReadDirEntry, Error>> + use<P, F>, Error>
+where
+    P: ?Sized + nix::NixPath,
+    F: Fn(&ReadDirEntry) -> bool,
+{
+    Ok(read_subdir(libc::AT_FDCWD, path)?.filter(move |item| match item {
+        Ok(entry) => {
+            let name = entry.file_name().to_bytes();
+            name != b"." && name != b".." && predicate(entry)
+        }
+        Err(_) => true,
+    }))
+}
+
 /// Scan through a directory with a regular expression. This is simply a shortcut filtering the
 /// results of `read_subdir`. Non-UTF8 compatible file names are silently ignored.
 pub fn scan_subdir<'a, P: ?Sized + nix::NixPath>(
@@ -354,3 +376,32 @@ pub fn get_file_type<P: ?Sized + nix::NixPath>(
         file_type_from_file_stat(&stat).ok_or_else(|| format_err!("unable to detect file type"))?;
     Ok(file_type)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_dir_filtered() -> Result<(), Error> {
+        let path = crate::fs::make_tmp_dir("/tmp", None)?;
+        for name in ["a.txt", "b.txt", "c.log", "d.txt.bak"] {
+            std::fs::write(path.join(name), name)?;
+        }
+        std::fs::create_dir(path.join("sub.txt"))?;
+
+        let mut files = read_dir_filtered(&path, |entry| {
+            entry.file_type() == Some(dir::Type::File)
+                && entry.file_name().to_bytes().ends_with(b".txt")
+        })?
+        .map(|entry| Ok(entry?.file_name().to_str()?.to_string()))
+        .collect::<Result<Vec<String>, Error>>()?;
+        files.sort();
+        assert_eq!(files, ["a.txt", "b.txt"]);
+
+        let all = read_dir_filtered(&path, |_| true)?.count();
+        assert_eq!(all, 5);
+
+        std::fs::remove_dir_all(&path)?;
+        Ok(())
+    }
+}
@@ -4,9 +4,11 @@ use std::convert::TryFrom;
 use std::mem::MaybeUninit;
 use std::num::NonZeroUsize;
 use std::os::fd::AsFd;
+use std::path::Path;
 use std::ptr::NonNull;
 use std::{io, mem};
 
+use anyhow::{Error, format_err};
 use nix::sys::mman;
 
 use proxmox_lang::io_format_err;
@@ -125,3 +127,125 @@ impl<T> Mmap<MaybeUninit<T>> {
         out
     }
 }
+
+impl Mmap<u8> {
+    /// Map an entire existing file read-write and shared, so that modifications end up in the
+    /// file.
+    ///
+    /// The file must not be empty. Use [`MmapMut::flush`] to synchronously write back changes.
+    pub fn map_file_rw<P: AsRef<Path>>(path: P) -> Result<MmapMut, Error> {
+        let path = path.as_ref();
+
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(|err| format_err!("unable to open {path:?} - {err}"))?;
+
+        let len = file
+            .metadata()
+            .map_err(|err| format_err!("unable to stat {path:?} - {err}"))?
+            .len();
+        let len = usize::try_from(len)
+            .map_err(|_| format_err!("file {path:?} too large to map ({len} bytes)"))?;
+
+        // SAFETY: `file` is a valid, open file, the mapping stays valid after it gets closed.
+        let inner = unsafe {
+            Self::map_fd(
+                &file,
+                0,
+                len,
+                mman::ProtFlags::PROT_READ | mman::ProtFlags::PROT_WRITE,
+                mman::MapFlags::MAP_SHARED,
+            )
+        }
+        .map_err(|err| format_err!("unable to map {path:?} - {err}"))?;
+
+        Ok(MmapMut { inner })
+    }
+}
+
+/// A shared, writable memory mapping of a whole file, see [`Mmap::map_file_rw`].
+///
+/// The mapping is removed when this is dropped. Changes are not guaranteed to have reached the
+/// file before [`flush`](MmapMut::flush) was called.
+pub struct MmapMut {
+    inner: Mmap<u8>,
+}
+
+impl MmapMut {
+    /// The length of the mapping in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Whether the mapping is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Access the mapped data.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.inner
+    }
+
+    /// Mutably access the mapped data.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.inner
+    }
+
+    /// Synchronously write back all modifications to the file (`msync(MS_SYNC)`).
+    pub fn flush(&self) -> Result<(), Error> {
+        self.inner
+            .msync(mman::MsFlags::MS_SYNC)
+            .map_err(|err| format_err!("failed to flush memory mapping - {err}"))
+    }
+}
+
+impl std::ops::Deref for MmapMut {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl std::ops::DerefMut for MmapMut {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Mmap;
+
+    #[test]
+    fn test_map_file_rw() -> Result<(), anyhow::Error> {
+        let dir = crate::fs::make_tmp_dir("/tmp", None)?;
+        let path = dir.join("mapped");
+        std::fs::write(&path, b"hello world")?;
+
+        let mut map = Mmap::map_file_rw(&path)?;
+        assert_eq!(map.as_slice(), b"hello world");
+        map.as_mut_slice()[..5].copy_from_slice(b"HELLO");
+        map.flush()?;
+
+        assert_eq!(std::fs::read(&path)?, b"HELLO world");
+        drop(map);
+
+        std::fs::write(dir.join("empty"), b"")?;
+        assert!(Mmap::map_file_rw(dir.join("empty")).is_err());
+        assert!(Mmap::map_file_rw(dir.join("missing")).is_err());
+
+        std::fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+}
@@ -199,6 +199,46 @@ impl CalendarEvent {
     }
 }
 
+/// Compute the next point in time at the given local weekday and time of day.
+///
+/// `weekday` counts from Monday (`0`) to Sunday (`6`). The result is the first matching Unix
+/// epoch strictly after `base`, if this week's occurrence has already passed this is the one in
+/// the following week.
+///
+/// # Examples
+///
+/// ```
+/// use proxmox_time::next_weekly;
+///
+/// let next = next_weekly(0, 0, 10, 30).unwrap();
+/// assert!(next > 0 && next <= 8 * 86400);
+/// assert!(next_weekly(0, 7, 10, 30).is_err());
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn next_weekly(base: i64, weekday: u32, hour: u32, minute: u32) -> Result<i64, Error> {
+    if weekday > 6 {
+        anyhow::bail!("invalid weekday {weekday}, expected 0 (monday) to 6 (sunday)");
+    }
+    if hour > 23 {
+        anyhow::bail!("invalid hour {hour}");
+    }
+    if minute > 59 {
+        anyhow::bail!("invalid minute {minute}");
+    }
+
+    let event = CalendarEvent {
+        days: WeekDays::from_bits_truncate(1 << weekday),
+        hour: vec![DateTimeValue::Single(hour)],
+        minute: vec![DateTimeValue::Single(minute)],
+        second: vec![DateTimeValue::Single(0)],
+        ..Default::default()
+    };
+
+    event
+        .compute_next_event(base)?
+        .ok_or_else(|| anyhow::format_err!("unable to compute next weekly event after {base}"))
+}
+
 impl std::str::FromStr for CalendarEvent {
     type Err = Error;
 
@@ -17,6 +17,7 @@
 //! - [`epoch_to_rfc3339`], [`epoch_to_rfc3339_utc`], [`epoch_to_rfc2822`] — epoch formatting
 //! - [`parse_rfc3339`] — RFC 3339 string to epoch
 //! - [`strftime`], [`strftime_l`] — safe `strftime` bindings
+//! - [`bucketize`] — grandfather-father-son retention selection
 
 #[cfg(not(target_arch = "wasm32"))]
 mod tm_editor;
@@ -39,11 +40,19 @@ pub use week_days::*;
 mod daily_duration;
 pub use daily_duration::*;
 
+mod utc_offset;
+pub use utc_offset::*;
+
 #[cfg(not(target_arch = "wasm32"))]
 mod posix;
 #[cfg(not(target_arch = "wasm32"))]
 pub use posix::*;
 
+#[cfg(not(target_arch = "wasm32"))]
+mod retention;
+#[cfg(not(target_arch = "wasm32"))]
+pub use retention::*;
+
 #[cfg(target_arch = "wasm32")]
 mod wasm;
 #[cfg(target_arch = "wasm32")]
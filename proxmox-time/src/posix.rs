@@ -108,6 +108,20 @@ pub fn epoch_f64() -> f64 {
     }
 }
 
+/// Sleep until the Unix Epoch `target` has been reached.
+///
+/// Returns immediately if `target` lies in the past. Since the system clock may be adjusted while
+/// sleeping, the remaining time is re-checked after every wakeup.
+pub fn sleep_until_epoch(target: i64) {
+    loop {
+        let remaining = target as f64 - epoch_f64();
+        if remaining <= 0.0 {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_secs_f64(remaining));
+    }
+}
+
 /// Safe bindings to libc strftime
 pub fn strftime(format: &str, t: &libc::tm) -> Result<String, Error> {
     let format = CString::new(format).map_err(|err| format_err!("{err}"))?;
@@ -516,3 +530,14 @@ fn test_epoch_to_rfc2822() {
     // Internally, it uses strftime_l which we test already.
     assert!(epoch_to_rfc2822(epoch).is_ok());
 }
+
+#[test]
+fn test_sleep_until_epoch() {
+    let start = epoch_i64();
+    sleep_until_epoch(start - 10);
+    assert!(epoch_i64() - start <= 1);
+
+    let target = start + 1;
+    sleep_until_epoch(target);
+    assert!(epoch_i64() >= target);
+}
@@ -0,0 +1,208 @@
+use std::collections::HashSet;
+
+use anyhow::Error;
+
+use crate::strftime_local;
+
+/// Number of entries to keep per retention period.
+///
+/// A count of `0` disables the respective period.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct KeepSpec {
+    /// Keep the newest `last` entries regardless of their time.
+    pub last: usize,
+    /// Keep the newest entry of each of the last `hourly` hours.
+    pub hourly: usize,
+    /// Keep the newest entry of each of the last `daily` days.
+    pub daily: usize,
+    /// Keep the newest entry of each of the last `weekly` ISO weeks.
+    pub weekly: usize,
+    /// Keep the newest entry of each of the last `monthly` months.
+    pub monthly: usize,
+    /// Keep the newest entry of each of the last `yearly` years.
+    pub yearly: usize,
+}
+
+impl KeepSpec {
+    /// Check whether no retention period is configured, meaning everything is kept.
+    pub fn keeps_all(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Select the timestamps to keep according to grandfather-father-son retention rules.
+///
+/// The periods are applied in the order `last`, `hourly`, `daily`, `weekly`, `monthly`,
+/// `yearly`. For each period the newest timestamp of a bucket is kept. Buckets which already
+/// contain a timestamp kept by a previous period are skipped and do not count towards the limit.
+/// Bucket boundaries are based on local time.
+///
+/// If `keep` does not configure any period, all timestamps are kept. The result is sorted
+/// newest first.
+pub fn bucketize(timestamps: &[i64], keep: KeepSpec) -> Result<Vec<i64>, Error> {
+    let mut list = timestamps.to_vec();
+    list.sort_unstable_by(|a, b| b.cmp(a));
+
+    if keep.keeps_all() {
+        return Ok(list);
+    }
+
+    let mut marks: Vec<Option<bool>> = vec![None; list.len()];
+
+    for mark in marks.iter_mut().take(keep.last) {
+        *mark = Some(true);
+    }
+
+    let periods = [
+        (keep.hourly, "%Y/%m/%d/%H"),
+        (keep.daily, "%Y/%m/%d"),
+        (keep.weekly, "%G/%V"),
+        (keep.monthly, "%Y/%m"),
+        (keep.yearly, "%Y"),
+    ];
+
+    for (count, format) in periods {
+        if count == 0 {
+            continue;
+        }
+        mark_buckets(&list, &mut marks, count, format)?;
+    }
+
+    Ok(list
+        .into_iter()
+        .zip(marks)
+        .filter_map(|(epoch, mark)| (mark == Some(true)).then_some(epoch))
+        .collect())
+}
+
+fn mark_buckets(
+    list: &[i64],
+    marks: &mut [Option<bool>],
+    count: usize,
+    format: &str,
+) -> Result<(), Error> {
+    let mut already_included = HashSet::new();
+    for (epoch, mark) in list.iter().zip(marks.iter()) {
+        if *mark == Some(true) {
+            already_included.insert(strftime_local(format, *epoch)?);
+        }
+    }
+
+    let mut included = HashSet::new();
+    for (epoch, mark) in list.iter().zip(marks.iter_mut()) {
+        if mark.is_some() {
+            continue;
+        }
+
+        let bucket = strftime_local(format, *epoch)?;
+        if already_included.contains(&bucket) {
+            continue;
+        }
+
+        if included.contains(&bucket) {
+            *mark = Some(false);
+        } else {
+            if included.len() >= count {
+                break;
+            }
+            included.insert(bucket);
+            *mark = Some(true);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TmEditor;
+
+    // two entries per day of March 2024, at 10:00 and 14:00 local time
+    fn march_2024() -> Vec<i64> {
+        let mut list = Vec::new();
+        for day in 1..=31 {
+            for hour in [10, 14] {
+                let mut tm = TmEditor::new(false);
+                tm.set_year(2024).unwrap();
+                tm.set_mon(3).unwrap();
+                tm.set_mday(day).unwrap();
+                tm.set_time(hour, 0, 0).unwrap();
+                list.push(tm.into_epoch().unwrap());
+            }
+        }
+        list
+    }
+
+    fn format(list: &[i64]) -> Vec<String> {
+        list.iter()
+            .map(|epoch| strftime_local("%m-%d %H", *epoch).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn bucketize_daily() {
+        let list = march_2024();
+
+        let keep = KeepSpec {
+            daily: 3,
+            ..Default::default()
+        };
+        assert_eq!(
+            format(&bucketize(&list, keep).unwrap()),
+            ["03-31 14", "03-30 14", "03-29 14"],
+        );
+
+        let keep = KeepSpec {
+            last: 2,
+            daily: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            format(&bucketize(&list, keep).unwrap()),
+            ["03-31 14", "03-31 10", "03-30 14", "03-29 14"],
+        );
+    }
+
+    #[test]
+    fn bucketize_weekly() {
+        let list = march_2024();
+
+        // 2024-03-31 is a sunday, so ISO weeks end on the 31st, 24th, 17th, ...
+        let keep = KeepSpec {
+            weekly: 3,
+            ..Default::default()
+        };
+        assert_eq!(
+            format(&bucketize(&list, keep).unwrap()),
+            ["03-31 14", "03-24 14", "03-17 14"],
+        );
+
+        // weeks already covered by daily entries do not count towards the weekly limit
+        let keep = KeepSpec {
+            daily: 7,
+            weekly: 3,
+            ..Default::default()
+        };
+        assert_eq!(
+            format(&bucketize(&list, keep).unwrap()),
+            [
+                "03-31 14", "03-30 14", "03-29 14", "03-28 14", "03-27 14", "03-26 14", "03-25 14",
+                "03-24 14", "03-17 14", "03-10 14",
+            ],
+        );
+
+        let keep = KeepSpec {
+            weekly: 10,
+            ..Default::default()
+        };
+        assert_eq!(bucketize(&list, keep).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn bucketize_keeps_all_without_spec() {
+        let list = [3, 1, 2];
+        assert_eq!(bucketize(&list, KeepSpec::default()).unwrap(), [3, 2, 1]);
+        assert!(bucketize(&[], KeepSpec::default()).unwrap().is_empty());
+    }
+}
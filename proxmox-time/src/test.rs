@@ -229,6 +229,38 @@ fn test_calendar_event_weekday() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn test_verify_calendar_event() {
+    for valid in [
+        "*-*-* 02:00:00",
+        "mon..fri 08..18:00",
+        "Mon..Fri 08..18:00",
+        "sat,sun 0/2:15",
+        "2024-01-15 12:30",
+        "*:0/5",
+        "hourly",
+        "daily UTC",
+    ] {
+        if let Err(err) = verify_calendar_event(valid) {
+            panic!("calendar event '{valid}' should be valid - {err}");
+        }
+    }
+
+    for invalid in [
+        "",
+        "mon..",
+        "funday",
+        "12:61",
+        "*-*-* 02:00:00 extra",
+        "2024-13-01",
+    ] {
+        assert!(
+            verify_calendar_event(invalid).is_err(),
+            "calendar event '{invalid}' should be invalid"
+        );
+    }
+}
+
 #[test]
 fn test_time_span_parser() -> Result<(), Error> {
     let test_value = |ts_str: &str, expect: f64| -> Result<(), Error> {
@@ -268,3 +300,39 @@ fn test_time_span_parser() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[test]
+fn test_next_weekly() -> Result<(), Error> {
+    // 2023-11-14, far enough from any DST switch
+    let lt = crate::localtime(1_700_000_000)?;
+    let midnight = 1_700_000_000 - i64::from(lt.tm_hour * 3600 + lt.tm_min * 60 + lt.tm_sec);
+    let base = midnight + 8 * 3600;
+    let weekday = ((lt.tm_wday + 6) % 7) as u32;
+
+    // later today
+    assert_eq!(
+        next_weekly(base, weekday, 12, 30)?,
+        base + 4 * 3600 + 30 * 60
+    );
+
+    // already passed today, so next week
+    assert_eq!(
+        next_weekly(base, weekday, 6, 0)?,
+        base + 7 * 86400 - 2 * 3600
+    );
+
+    // strictly after the base time
+    assert_eq!(next_weekly(base, weekday, 8, 0)?, base + 7 * 86400);
+
+    // a later day this week
+    assert_eq!(
+        next_weekly(base, (weekday + 2) % 7, 8, 0)?,
+        base + 2 * 86400
+    );
+
+    assert!(next_weekly(base, 7, 0, 0).is_err());
+    assert!(next_weekly(base, 0, 24, 0).is_err());
+    assert!(next_weekly(base, 0, 0, 60).is_err());
+
+    Ok(())
+}
@@ -115,7 +115,7 @@
 //!   and nanoseconds).
 //! - [`From<TimeSpan> for std::time::Duration`] is likewise trivial and infallible.
 
-use anyhow::{Context, Error};
+use anyhow::{Context, Error, bail, format_err};
 
 use crate::parse_helpers::{IResult, parse_complete_line, parse_error};
 
@@ -872,6 +872,71 @@ pub fn verify_time_span(i: &str) -> Result<(), Error> {
     Ok(())
 }
 
+/// Parse a time relative to `base` and return it as epoch.
+///
+/// The expression must start with the `now` anchor (referring to `base`), optionally followed by
+/// `+` or `-` and a [TimeSpan], for example `now`, `now-7d` or `now+1d 12h`.
+pub fn parse_relative(expr: &str, base: i64) -> Result<i64, Error> {
+    let expr = expr.trim();
+    let Some(rest) = expr.strip_prefix("now") else {
+        bail!("unknown anchor in relative time {expr:?}, expected 'now'");
+    };
+
+    let rest = rest.trim_start();
+    let (negative, span) = if let Some(span) = rest.strip_prefix('-') {
+        (true, span)
+    } else if let Some(span) = rest.strip_prefix('+') {
+        (false, span)
+    } else if rest.is_empty() {
+        return Ok(base);
+    } else {
+        bail!("invalid relative time {expr:?}, expected '+' or '-' after 'now'");
+    };
+
+    let span: TimeSpan = span.trim_start().parse()?;
+    let offset = i64::try_from(span.as_secs())
+        .map_err(|_| format_err!("relative time {expr:?} out of range"))?;
+
+    if negative {
+        base.checked_sub(offset)
+    } else {
+        base.checked_add(offset)
+    }
+    .ok_or_else(|| format_err!("relative time {expr:?} out of range"))
+}
+
+/// Format the time between `epoch` and `now` as a coarse, human readable string, such as
+/// `"3 minutes ago"` or `"in 2 hours"`.
+///
+/// Only the largest unit fitting into the difference is used, and the count is rounded down.
+/// Differences of less than a minute, in either direction, result in `"just now"`.
+pub fn format_ago(epoch: i64, now: i64) -> String {
+    const UNITS: [(u64, &str); 6] = [
+        (SECS_PER_YEAR, "year"),
+        (SECS_PER_MONTH, "month"),
+        (SECS_PER_WEEK, "week"),
+        (SECS_PER_DAY, "day"),
+        (SECS_PER_HOUR, "hour"),
+        (SECS_PER_MINUTE, "minute"),
+    ];
+
+    let delta = now.abs_diff(epoch);
+    let Some((count, unit)) = UNITS
+        .iter()
+        .find(|(secs, _)| delta >= *secs)
+        .map(|(secs, unit)| (delta / secs, unit))
+    else {
+        return "just now".to_string();
+    };
+
+    let plural = if count == 1 { "" } else { "s" };
+    if epoch <= now {
+        format!("{count} {unit}{plural} ago")
+    } else {
+        format!("in {count} {unit}{plural}")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1374,4 +1439,47 @@ mod tests {
         let ts = TimeSpan::from_str("1h 3s").unwrap();
         assert_eq!(ts.display_down_to(TimeUnit::Seconds).to_string(), "1h 3s");
     }
+
+    #[test]
+    fn test_parse_relative() {
+        const BASE: i64 = 1_700_000_000;
+
+        assert_eq!(parse_relative("now", BASE).unwrap(), BASE);
+        assert_eq!(parse_relative(" now ", BASE).unwrap(), BASE);
+        assert_eq!(parse_relative("now-7d", BASE).unwrap(), BASE - 7 * 86400);
+        assert_eq!(parse_relative("now+2h", BASE).unwrap(), BASE + 2 * 3600);
+        assert_eq!(
+            parse_relative("now - 1d 2h 30m", BASE).unwrap(),
+            BASE - 86400 - 2 * 3600 - 30 * 60
+        );
+        assert_eq!(parse_relative("now+90", BASE).unwrap(), BASE + 90);
+
+        assert!(parse_relative("today-1d", BASE).is_err());
+        assert!(parse_relative("-1d", BASE).is_err());
+        assert!(parse_relative("now1d", BASE).is_err());
+        assert!(parse_relative("now-", BASE).is_err());
+        assert!(parse_relative("now-1x", BASE).is_err());
+        assert!(parse_relative("now+1000000y", i64::MAX - 1).is_err());
+    }
+
+    #[test]
+    fn format_ago_deltas() {
+        let now = 1_700_000_000;
+        for (delta, past, future) in [
+            (0, "just now", "just now"),
+            (59, "just now", "just now"),
+            (60, "1 minute ago", "in 1 minute"),
+            (3 * 60 + 59, "3 minutes ago", "in 3 minutes"),
+            (2 * 3600, "2 hours ago", "in 2 hours"),
+            (36 * 3600, "1 day ago", "in 1 day"),
+            (15 * 86400, "2 weeks ago", "in 2 weeks"),
+            (90 * 86400, "2 months ago", "in 2 months"),
+            (3 * 366 * 86400, "3 years ago", "in 3 years"),
+        ] {
+            assert_eq!(format_ago(now - delta, now), past, "delta {delta}");
+            assert_eq!(format_ago(now + delta, now), future, "delta -{delta}");
+        }
+
+        assert_eq!(format_ago(i64::MIN, i64::MAX), "584542046090 years ago");
+    }
 }
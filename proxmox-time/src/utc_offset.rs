@@ -0,0 +1,87 @@
+use anyhow::{Error, bail, format_err};
+
+/// Largest UTC offset in use (`+14:00`, Line Islands).
+const MAX_UTC_OFFSET: i32 = 14 * 3600;
+
+/// Parse a UTC offset like `"+02:00"`, `"-0530"` or `"Z"` into seconds east of UTC.
+///
+/// Offsets beyond ±14 hours are rejected.
+pub fn parse_utc_offset(s: &str) -> Result<i32, Error> {
+    parse_utc_offset_do(s).map_err(|err| format_err!("invalid UTC offset {s:?} - {err}"))
+}
+
+fn parse_utc_offset_do(s: &str) -> Result<i32, Error> {
+    if s == "Z" {
+        return Ok(0);
+    }
+
+    let (sign, rest) = match s.as_bytes().first() {
+        Some(b'+') => (1, &s[1..]),
+        Some(b'-') => (-1, &s[1..]),
+        _ => bail!("expected 'Z' or a sign"),
+    };
+
+    if !rest.is_ascii() {
+        bail!("unexpected character");
+    }
+
+    let (hours, minutes) = match rest.len() {
+        5 if rest.as_bytes()[2] == b':' => (&rest[..2], &rest[3..]),
+        4 => (&rest[..2], &rest[2..]),
+        _ => bail!("expected HH:MM or HHMM"),
+    };
+
+    let number = |digits: &str| -> Result<i32, Error> {
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            bail!("unexpected character");
+        }
+        Ok(digits.parse()?)
+    };
+
+    let hours = number(hours)?;
+    let minutes = number(minutes)?;
+    if minutes > 59 {
+        bail!("minutes out of range");
+    }
+
+    let offset = hours * 3600 + minutes * 60;
+    if offset > MAX_UTC_OFFSET {
+        bail!("offset out of range");
+    }
+
+    Ok(sign * offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_utc_offset;
+
+    #[test]
+    fn test_parse_utc_offset() {
+        assert_eq!(parse_utc_offset("Z").unwrap(), 0);
+        assert_eq!(parse_utc_offset("+00:00").unwrap(), 0);
+        assert_eq!(parse_utc_offset("-00:00").unwrap(), 0);
+        assert_eq!(parse_utc_offset("+02:00").unwrap(), 7200);
+        assert_eq!(parse_utc_offset("-0530").unwrap(), -(5 * 3600 + 30 * 60));
+        assert_eq!(parse_utc_offset("+0545").unwrap(), 5 * 3600 + 45 * 60);
+        assert_eq!(parse_utc_offset("+14:00").unwrap(), 14 * 3600);
+        assert_eq!(parse_utc_offset("-12:00").unwrap(), -12 * 3600);
+    }
+
+    #[test]
+    fn test_parse_utc_offset_invalid() {
+        // out of range
+        assert!(parse_utc_offset("+14:01").is_err());
+        assert!(parse_utc_offset("-15:00").is_err());
+        assert!(parse_utc_offset("+99:00").is_err());
+        assert!(parse_utc_offset("+02:60").is_err());
+
+        // malformed
+        for input in [
+            "", "z", "02:00", "+2:00", "+02", "+02:0", "+02-00", "+0a:00", "+ 2:00", "+02:00Z",
+            "+aé0",
+        ] {
+            assert!(parse_utc_offset(input).is_err(), "{input:?} was accepted");
+        }
+    }
+}
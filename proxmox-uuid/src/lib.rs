@@ -39,6 +39,9 @@ fn hex_digit(b: u8) -> Result<u8, UuidError> {
 
 /// Uuid generated with the system's native libuuid.
 ///
+/// Comparison and ordering are based on the raw bytes, so uuids can be used as keys in hash and
+/// b-tree maps and sort in byte order.
+///
 /// ```
 /// use proxmox_uuid::Uuid;
 ///
@@ -55,16 +58,44 @@ fn hex_digit(b: u8) -> Result<u8, UuidError> {
 /// let parsed: Uuid = text.parse().unwrap();
 /// assert_eq!(uuid, parsed);
 /// ```
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct Uuid(Box<[u8; 16]>);
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Uuid([u8; 16]);
 
 impl Uuid {
     /// Generate a uuid with `uuid_generate(3)`.
     pub fn generate() -> Self {
-        use std::alloc::{Layout, alloc};
-        let uuid = unsafe { alloc(Layout::new::<[u8; 16]>()) as *mut [u8; 16] };
-        unsafe { uuid_generate(uuid) };
-        Self(unsafe { Box::from_raw(uuid) })
+        let mut uuid = [0u8; 16];
+        unsafe { uuid_generate(&mut uuid) };
+        Self(uuid)
+    }
+
+    /// Generate a random (version 4) uuid using the random number generator `rng`.
+    ///
+    /// In contrast to [`generate`](Uuid::generate) this allows choosing the source of randomness,
+    /// e.g. a seeded generator to get reproducible uuids in tests.
+    #[cfg(feature = "rand")]
+    pub fn generate_v4_from<R: rand_core::RngCore + ?Sized>(rng: &mut R) -> Self {
+        let mut uuid = [0u8; 16];
+        rng.fill_bytes(&mut uuid);
+        // set the version (RFC 9562 section 5.4) and variant (section 4.1) bits
+        uuid[6] = (uuid[6] & 0x0f) | 0x40;
+        uuid[8] = (uuid[8] & 0x3f) | 0x80;
+        Self(uuid)
+    }
+
+    /// The nil uuid with all bits set to zero, see RFC 9562 section 5.9.
+    pub const fn nil() -> Self {
+        Self([0u8; 16])
+    }
+
+    /// The max uuid with all bits set to one, see RFC 9562 section 5.10.
+    pub const fn max() -> Self {
+        Self([0xff; 16])
+    }
+
+    /// Check whether this is the [nil](Uuid::nil) uuid.
+    pub const fn is_nil(&self) -> bool {
+        u128::from_ne_bytes(self.0) == 0
     }
 
     /// Get a reference to the internal 16 byte array.
@@ -72,9 +103,11 @@ impl Uuid {
         &self.0
     }
 
-    /// Take out the inner boxed 16 byte array.
+    /// Get the 16 byte array in a box.
+    ///
+    /// The bytes are stored inline, so this allocates. Convert into a `[u8; 16]` to avoid that.
     pub fn into_inner(self) -> Box<[u8; 16]> {
-        self.0
+        Box::new(self.0)
     }
 
     /// Parse a uuid in optionally-hyphenated format.
@@ -92,12 +125,10 @@ impl Uuid {
     /// assert_eq!(uuid1, uuid2);
     /// ```
     pub fn parse_str(src: &str) -> Result<Self, UuidError> {
-        use std::alloc::{Layout, alloc};
-        let uuid: *mut [u8; 16] = unsafe { alloc(Layout::new::<[u8; 16]>()) as *mut [u8; 16] };
+        let mut uuid = [0u8; 16];
         if src.len() == 36 {
             // Unfortunately the manpage of `uuid_parse(3)` states that it technically requires a
             // terminating null byte at the end, which we don't have, so do this manually:
-            let uuid: &mut [u8] = unsafe { &mut (&mut *uuid)[..] };
             let src = src.as_bytes();
             if src[8] != b'-' || src[13] != b'-' || src[18] != b'-' || src[23] != b'-' {
                 return Err(UuidError);
@@ -118,7 +149,6 @@ impl Uuid {
                 uuid[i] = (hex_digit(src[2 * i + 4])? << 4) | hex_digit(src[2 * i + 5])?;
             }
         } else if src.len() == 32 {
-            let uuid: &mut [u8] = unsafe { &mut (&mut *uuid)[..] };
             let src = src.as_bytes();
             for i in 0..16 {
                 uuid[i] = (hex_digit(src[2 * i])? << 4) | hex_digit(src[2 * i + 1])?;
@@ -126,10 +156,27 @@ impl Uuid {
         } else {
             return Err(UuidError);
         }
-        Ok(Self(unsafe { Box::from_raw(uuid) }))
+        Ok(Self(uuid))
+    }
+
+    /// Format the uuid as URN (`urn:uuid:<hyphenated>`, see RFC 9562).
+    ///
+    /// ```
+    /// use proxmox_uuid::Uuid;
+    ///
+    /// let uuid: Uuid = "65b85639-78d7-4330-85c6-39502b2f9b01".parse().unwrap();
+    /// assert_eq!(
+    ///     uuid.to_urn_string(),
+    ///     "urn:uuid:65b85639-78d7-4330-85c6-39502b2f9b01",
+    /// );
+    /// ```
+    pub fn to_urn_string(&self) -> String {
+        format!("{URN_PREFIX}{self}")
     }
 }
 
+const URN_PREFIX: &str = "urn:uuid:";
+
 impl AsRef<[u8]> for Uuid {
     #[inline]
     fn as_ref(&self) -> &[u8] {
@@ -160,19 +207,19 @@ impl BorrowMut<[u8]> for Uuid {
 
 impl From<[u8; 16]> for Uuid {
     fn from(data: [u8; 16]) -> Self {
-        Self(Box::new(data))
+        Self(data)
     }
 }
 
 impl From<Box<[u8; 16]>> for Uuid {
     fn from(data: Box<[u8; 16]>) -> Self {
-        Self(data)
+        Self(*data)
     }
 }
 
 impl From<Uuid> for [u8; 16] {
     fn from(this: Uuid) -> [u8; 16] {
-        *this.0
+        this.0
     }
 }
 
@@ -185,8 +232,14 @@ impl fmt::Display for Uuid {
 impl std::str::FromStr for Uuid {
     type Err = UuidError;
 
+    /// Parse a uuid in optionally-hyphenated format, optionally in URN form (`urn:uuid:...`).
     fn from_str(src: &str) -> Result<Self, UuidError> {
-        Self::parse_str(src)
+        match src.get(..URN_PREFIX.len()) {
+            Some(prefix) if prefix.eq_ignore_ascii_case(URN_PREFIX) => {
+                Self::parse_str(&src[URN_PREFIX.len()..])
+            }
+            _ => Self::parse_str(src),
+        }
     }
 }
 
@@ -234,3 +287,122 @@ fn test_uuid_serde() {
     let de: Uuid = serde_json::from_str(&ser).expect("failed to deserialize uuid");
     assert_eq!(uuid, de);
 }
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_uuid_generate_v4_from() {
+    /// Minimal seedable generator (splitmix64) for reproducible output.
+    struct TestRng(u64);
+
+    impl rand_core::RngCore for TestRng {
+        fn next_u32(&mut self) -> u32 {
+            self.next_u64() as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            z ^ (z >> 31)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            rand_core::impls::fill_bytes_via_next(self, dest)
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    let first = Uuid::generate_v4_from(&mut TestRng(42));
+    assert_eq!(first, Uuid::generate_v4_from(&mut TestRng(42)));
+    assert_ne!(first, Uuid::generate_v4_from(&mut TestRng(43)));
+
+    let mut rng = TestRng(42);
+    let _ = Uuid::generate_v4_from(&mut rng);
+    assert_ne!(first, Uuid::generate_v4_from(&mut rng));
+
+    let bytes = first.as_bytes();
+    assert_eq!(bytes[6] >> 4, 4, "version must be 4");
+    assert_eq!(bytes[8] >> 6, 0b10, "variant must be RFC 9562");
+}
+
+#[test]
+fn test_uuid_ordering() {
+    use std::collections::BTreeMap;
+
+    let mut bytes = [[0u8; 16], [0u8; 16], [0u8; 16], [0u8; 16]];
+    bytes[0][0] = 0x80;
+    bytes[1][15] = 0x01;
+    bytes[2][0] = 0x01;
+    bytes[3][1] = 0xff;
+
+    let mut map = BTreeMap::new();
+    for (i, b) in bytes.iter().enumerate() {
+        map.insert(Uuid::from(*b), i);
+    }
+
+    let mut sorted = bytes;
+    sorted.sort();
+    let keys: Vec<[u8; 16]> = map.keys().map(|uuid| *uuid.as_bytes()).collect();
+    assert_eq!(keys, sorted);
+
+    let uuid = Uuid::from(bytes[3]);
+    let copy = uuid;
+    assert_eq!(map[&uuid], 3);
+    assert_eq!(map[&copy], 3);
+}
+
+#[test]
+fn test_uuid_special() {
+    assert!(Uuid::nil().is_nil());
+    assert!(!Uuid::max().is_nil());
+    assert!(!Uuid::generate().is_nil());
+    assert!(Uuid::nil() < Uuid::max());
+
+    assert_eq!(
+        Uuid::nil().to_string(),
+        "00000000-0000-0000-0000-000000000000"
+    );
+    assert_eq!(
+        Uuid::max().to_string(),
+        "ffffffff-ffff-ffff-ffff-ffffffffffff"
+    );
+    assert_eq!(
+        "ffffffff-ffff-ffff-ffff-ffffffffffff"
+            .parse::<Uuid>()
+            .unwrap(),
+        Uuid::max()
+    );
+}
+
+#[test]
+fn test_uuid_urn() {
+    let uuid = Uuid::generate();
+    let urn = uuid.to_urn_string();
+    assert!(urn.starts_with("urn:uuid:"));
+    assert_eq!(urn.len(), 45);
+    assert_eq!(urn.parse::<Uuid>().unwrap(), uuid);
+
+    let plain: Uuid = "65b85639-78d7-4330-85c6-39502b2f9b01".parse().unwrap();
+    let urn: Uuid = "URN:UUID:65b85639-78d7-4330-85c6-39502b2f9b01"
+        .parse()
+        .unwrap();
+    assert_eq!(plain, urn);
+    assert_eq!(
+        "urn:uuid:65b8563978d7433085c639502b2f9b01"
+            .parse::<Uuid>()
+            .unwrap(),
+        plain
+    );
+
+    assert!("urn:uuid:".parse::<Uuid>().is_err());
+    assert!(
+        "urn:65b85639-78d7-4330-85c6-39502b2f9b01"
+            .parse::<Uuid>()
+            .is_err()
+    );
+}